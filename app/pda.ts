@@ -0,0 +1,174 @@
+import { createHash } from "crypto";
+import { PublicKey } from "@solana/web3.js";
+
+// ============================================================================
+// PDA derivation helpers for the basilisk-escrow program.
+//
+// Per-job PDAs are namespaced by the requester's pubkey, so two requesters can
+// use the same job_id and nobody can squat a job_id planned by someone else.
+// The job_id itself is seeded as its SHA-256 hash, since a raw seed is capped
+// at 32 bytes and a canonical UUID is 36.
+// ============================================================================
+
+export function jobIdHash(jobId: string): Buffer {
+  return createHash("sha256").update(jobId).digest();
+}
+
+export function findConfigPDA(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+}
+
+export function findJobPDA(
+  programId: PublicKey,
+  requester: PublicKey,
+  jobId: string
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("job"), requester.toBuffer(), jobIdHash(jobId)],
+    programId
+  );
+}
+
+export function findEscrowAuthorityPDA(
+  programId: PublicKey,
+  requester: PublicKey,
+  jobId: string
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow"), requester.toBuffer(), jobIdHash(jobId)],
+    programId
+  );
+}
+
+export function findEscrowTokenPDA(
+  programId: PublicKey,
+  requester: PublicKey,
+  jobId: string
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow_token"), requester.toBuffer(), jobIdHash(jobId)],
+    programId
+  );
+}
+
+export function findSolVaultPDA(
+  programId: PublicKey,
+  requester: PublicKey,
+  jobId: string
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("sol_vault"), requester.toBuffer(), jobIdHash(jobId)],
+    programId
+  );
+}
+
+export function findBondTokenPDA(
+  programId: PublicKey,
+  requester: PublicKey,
+  jobId: string
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("bond_token"), requester.toBuffer(), jobIdHash(jobId)],
+    programId
+  );
+}
+
+export function findBondVaultPDA(
+  programId: PublicKey,
+  requester: PublicKey,
+  jobId: string
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("bond_vault"), requester.toBuffer(), jobIdHash(jobId)],
+    programId
+  );
+}
+
+export function findApplicationPDA(
+  programId: PublicKey,
+  job: PublicKey,
+  agent: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("application"), job.toBuffer(), agent.toBuffer()],
+    programId
+  );
+}
+
+export function findArbitratorRegistrationPDA(
+  programId: PublicKey,
+  arbitrator: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("arbitrator"), arbitrator.toBuffer()],
+    programId
+  );
+}
+
+export function findDisputeVotePDA(programId: PublicKey, job: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("dispute_vote"), job.toBuffer()],
+    programId
+  );
+}
+
+export function findEvidencePDA(
+  programId: PublicKey,
+  job: PublicKey,
+  party: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("evidence"), job.toBuffer(), party.toBuffer()],
+    programId
+  );
+}
+
+export function findArtifactsPDA(programId: PublicKey, job: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("artifacts"), job.toBuffer()],
+    programId
+  );
+}
+
+export function findAgentProfilePDA(programId: PublicKey, agent: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("agent_profile"), agent.toBuffer()],
+    programId
+  );
+}
+
+export function findRequesterProfilePDA(
+  programId: PublicKey,
+  requester: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("requester_profile"), requester.toBuffer()],
+    programId
+  );
+}
+
+export function findTreasuryPDA(programId: PublicKey, mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury"), mint.toBuffer()],
+    programId
+  );
+}
+
+export function findSolTreasuryPDA(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("sol_treasury")], programId);
+}
+
+// Archives are also seeded with the job's created_at (i64, little-endian),
+// since a closed job_id can be re-created at the same Job address.
+export function findArchivePDA(
+  programId: PublicKey,
+  job: PublicKey,
+  createdAt: number | bigint
+): [PublicKey, number] {
+  const createdAtBytes = Buffer.alloc(8);
+  createdAtBytes.writeBigInt64LE(BigInt(createdAt));
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("archive"), job.toBuffer(), createdAtBytes],
+    programId
+  );
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+/// Anchor numbers error codes by position (6000 + index), so new variants
+/// are appended at the end; inserting one would renumber every code after it
+/// and break deployed clients.
 #[error_code]
 pub enum EscrowError {
     // ── Status errors ───────────────────────────────────────────────────
@@ -22,7 +25,7 @@ pub enum EscrowError {
     #[msg("Unauthorized: signer does not match required authority")]
     Unauthorized,
 
-    #[msg("Unauthorized arbitrator: signer is not the authorized arbitrator")]
+    #[msg("Unauthorized arbitrator: signer is not on the arbitrator panel")]
     UnauthorizedArbitrator,
 
     // ── Validation errors ───────────────────────────────────────────────
@@ -45,7 +48,7 @@ pub enum EscrowError {
     #[msg("Description exceeds maximum length of 200 characters")]
     DescriptionTooLong,
 
-    #[msg("Deliverable data exceeds maximum length of 500 characters")]
+    #[msg("Deliverable URI or content type exceeds its maximum length")]
     DeliverableTooLong,
 
     // ── Arithmetic errors ───────────────────────────────────────────────
@@ -57,4 +60,144 @@ pub enum EscrowError {
 
     #[msg("Job deadline has expired")]
     DeadlineExpired,
+
+    // ── Job closing ─────────────────────────────────────────────────────
+    #[msg("Job must be Completed, Cancelled or Resolved to be closed")]
+    JobNotTerminal,
+
+    // ── Protocol fees ───────────────────────────────────────────────────
+    #[msg("Fee exceeds the maximum of 1000 basis points")]
+    FeeTooHigh,
+
+    #[msg("Treasury balance is insufficient for this withdrawal")]
+    InsufficientTreasuryBalance,
+
+    // ── Native SOL escrow ───────────────────────────────────────────────
+    #[msg("Recipient account does not match expected party")]
+    InvalidRecipient,
+
+    #[msg("An account required for this job's asset kind was not provided")]
+    MissingAccount,
+
+    // ── Milestones ──────────────────────────────────────────────────────
+    #[msg("Milestone index does not match the job's current milestone")]
+    InvalidMilestone,
+
+    #[msg("Milestone amounts must sum to the job amount")]
+    MilestoneAmountMismatch,
+
+    #[msg("Milestone deadline must not be later than the job deadline")]
+    InvalidMilestoneDeadline,
+
+    #[msg("Job exceeds the maximum of 10 milestones")]
+    TooManyMilestones,
+
+    // ── Private jobs ────────────────────────────────────────────────────
+    #[msg("Agent is not invited to this private job")]
+    AgentNotInvited,
+
+    #[msg("Invalid agent pubkey")]
+    InvalidAgent,
+
+    #[msg("Job exceeds the maximum of 5 invited agents")]
+    TooManyAllowedAgents,
+
+    // ── Applications ────────────────────────────────────────────────────
+    #[msg("Application does not belong to this job")]
+    InvalidApplication,
+
+    // ── Requester ratings ───────────────────────────────────────────────
+    #[msg("This job has already been rated")]
+    AlreadyRated,
+
+    // ── Arbitrator panel ────────────────────────────────────────────────
+    #[msg("Arbitrator has already voted on this dispute")]
+    AlreadyVoted,
+
+    #[msg("Invalid or duplicate arbitrator pubkey")]
+    InvalidArbitrator,
+
+    #[msg("Threshold must be between 1 and the number of arbitrators")]
+    InvalidThreshold,
+
+    #[msg("Arbitrator panel exceeds the maximum of 5 members")]
+    TooManyArbitrators,
+
+    // ── Arbitrator registry ─────────────────────────────────────────────
+    #[msg("Arbitrator is not in the arbitrator registry")]
+    ArbitratorNotRegistered,
+
+    // ── Review window ───────────────────────────────────────────────────
+    #[msg("Review window must be at least one day")]
+    InvalidReviewWindow,
+
+    #[msg("Review window has not elapsed yet")]
+    ReviewWindowOpen,
+
+    #[msg("Review window has elapsed")]
+    ReviewWindowExpired,
+
+    // ── Dispute window ──────────────────────────────────────────────────
+    #[msg("Dispute window must be at least one day")]
+    InvalidDisputeWindow,
+
+    #[msg("Dispute window has not elapsed yet")]
+    DisputeWindowOpen,
+
+    // ── Dispute evidence ────────────────────────────────────────────────
+    #[msg("Evidence URI exceeds maximum length of 128 characters")]
+    EvidenceUriTooLong,
+
+    #[msg("Party already submitted the maximum of 5 evidence items")]
+    TooManyEvidenceItems,
+
+    #[msg("Evidence can only be submitted before the dispute deadline")]
+    EvidenceWindowClosed,
+
+    // ── Deadline extensions ─────────────────────────────────────────────
+    #[msg("Deadline extension must move the deadline later")]
+    InvalidExtension,
+
+    #[msg("No matching deadline extension has been proposed")]
+    NoExtensionProposed,
+
+    #[msg("Job has used the maximum of 3 deadline extensions")]
+    ExtensionLimitReached,
+
+    // ── Settlement ──────────────────────────────────────────────────────
+    #[msg("No matching settlement has been proposed")]
+    NoSettlementProposed,
+
+    // ── Revisions ───────────────────────────────────────────────────────
+    #[msg("Job has used all of its revision requests")]
+    RevisionLimitReached,
+
+    #[msg("Revisions remain; request a revision or force the dispute")]
+    RevisionsRemaining,
+
+    // ── Deliverables ────────────────────────────────────────────────────
+    #[msg("Deliverable content hash must not be empty")]
+    MissingContentHash,
+
+    #[msg("Submission exceeds the maximum of 8 extra artifacts")]
+    TooManyArtifacts,
+
+    #[msg("Feedback exceeds maximum length of 200 characters")]
+    FeedbackTooLong,
+
+    // ── Job deadlines ───────────────────────────────────────────────────
+    #[msg("Job deadline must be at least one day")]
+    InvalidDeadline,
+
+    // ── Upgrades ────────────────────────────────────────────────────────
+    #[msg("Config already uses the current layout")]
+    ConfigAlreadyMigrated,
+
+    // ── Agent bonds ─────────────────────────────────────────────────────
+    #[msg("Set either a fixed bond or a bond of at most 10000 basis points")]
+    InvalidBond,
+
+    // ── Requester ratings ───────────────────────────────────────────────
+    #[msg("The agent can still rate the requester; close after the rating window")]
+    RatingWindowOpen,
 }
@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use crate::state::{ArbitrationFee, DisputeFallback};
+
+// ============================================================================
+// EVENTS - One typed event per state transition, for off-chain indexers.
+// ============================================================================
+
+#[event]
+pub struct JobCreated {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub requester: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub deadline: i64,
+}
+
+#[event]
+pub struct EscrowIncreased {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub added: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct JobAccepted {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub agent: Pubkey,
+    pub bond: u64,
+}
+
+#[event]
+pub struct ApplicationSubmitted {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub agent: Pubkey,
+    pub proposed_amount: u64,
+    pub eta_days: u8,
+}
+
+#[event]
+pub struct AgentUnselected {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub agent: Pubkey,
+}
+
+#[event]
+pub struct DeliverableSubmitted {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub agent: Pubkey,
+    pub milestone: u8,
+    pub content_hash: [u8; 32],
+    pub uri: String,
+    pub content_type: String,
+    pub artifact_count: u8,
+}
+
+#[event]
+pub struct JobApproved {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub agent: Pubkey,
+    pub milestone: u8,
+    pub amount: u64,
+    pub fee: u64,
+    pub rating: u8,
+    pub completed: bool,
+    pub bond_returned: u64,
+    pub auto_approved: bool,
+}
+
+#[event]
+pub struct DeadlineExtensionProposed {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub proposer: Pubkey,
+    pub new_deadline: i64,
+}
+
+#[event]
+pub struct DeadlineExtended {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub accepted_by: Pubkey,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+    pub extensions: u8,
+}
+
+#[event]
+pub struct RevisionRequested {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub milestone: u8,
+    pub feedback: String,
+    pub revisions: u8,
+    pub resubmit_by: i64,
+}
+
+#[event]
+pub struct JobRejected {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub milestone: u8,
+    pub reason: String,
+    pub dispute_deadline: i64,
+}
+
+#[event]
+pub struct JobCancelled {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub refund_amount: u64,
+    pub bond_forfeited: u64,
+}
+
+#[event]
+pub struct JobAbandoned {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub agent: Pubkey,
+    pub bond_forfeited: u64,
+}
+
+#[event]
+pub struct DisputeVoteCast {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub arbitrator: Pubkey,
+    pub agent_percentage: u8,
+    pub bond_agent_percentage: u8,
+    pub votes: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub agent_amount: u64,
+    pub requester_amount: u64,
+    pub fee: u64,
+    pub arbitration_fee: u64,
+    pub agent_percentage: u8,
+    pub bond_agent_amount: u64,
+    pub bond_requester_amount: u64,
+}
+
+#[event]
+pub struct EvidenceSubmitted {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub party: Pubkey,
+    pub content_hash: [u8; 32],
+    pub uri: String,
+    pub count: u8,
+}
+
+#[event]
+pub struct DisputeExpired {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub agent_amount: u64,
+    pub requester_amount: u64,
+    pub fee: u64,
+    pub agent_percentage: u8,
+    pub bond_agent_amount: u64,
+    pub bond_requester_amount: u64,
+}
+
+#[event]
+pub struct SettlementProposed {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub proposer: Pubkey,
+    pub agent_percentage: u8,
+    pub bond_agent_percentage: u8,
+}
+
+#[event]
+pub struct JobSettled {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub accepted_by: Pubkey,
+    pub agent_amount: u64,
+    pub requester_amount: u64,
+    pub fee: u64,
+    pub agent_percentage: u8,
+    pub bond_agent_amount: u64,
+    pub bond_requester_amount: u64,
+}
+
+#[event]
+pub struct RequesterRated {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub requester: Pubkey,
+    pub agent: Pubkey,
+    pub rating: u8,
+}
+
+#[event]
+pub struct JobClosed {
+    pub job: Pubkey,
+    pub job_id: String,
+    pub archived: bool,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub arbitrators: Vec<Pubkey>,
+    pub threshold: u8,
+    pub require_registered_arbitrator: bool,
+    pub arbitration_fee: ArbitrationFee,
+    pub dispute_fallback: DisputeFallback,
+    pub fee_bps: u16,
+}
+
+#[event]
+pub struct ArbitratorRegistered {
+    pub arbitrator: Pubkey,
+    pub registered: bool,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::utils::{close_artifacts, Escrow};
+use crate::events::JobAbandoned;
+
+/// Agent walks away from a job they accepted, reopening it for others.
+///
+/// The job goes back to Open with no agent, keeping its escrow, deadline and
+/// any milestones already paid, so another agent can pick it up without the
+/// requester re-funding it. The next agent starts with a fresh revision
+/// allowance. If the deadline has already passed, accept_job and
+/// select_agent refuse the reopened job, leaving the requester to cancel
+/// it and recover the escrow. Walking away is penalized: any bond the agent
+/// posted is forfeited to the requester, and the abandonment is counted on
+/// the agent's AgentProfile. A bonded job asks the next agent for a fresh
+/// bond in accept_job.
+///
+/// Only work in progress can be abandoned; once a deliverable is under
+/// review or disputed it must be settled instead. Bonded token jobs pass
+/// `requester_token` and the bond accounts; bonded native SOL jobs pass
+/// `requester` and `bond_vault`. The job's `artifacts` PDA is always passed;
+/// if the agent already submitted artifacts (and was sent back for a
+/// revision) the record is closed and its rent returned to the agent.
+///
+/// SECURITY:
+/// - PDA seed validation on job account
+/// - has_one = agent: only the assigned agent can abandon
+/// - Bond escrow validated by PDA seeds; forfeit recipient validated
+///   against job.requester
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, AbandonJob<'info>>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+
+    require!(
+        job.status == JobStatus::InProgress,
+        EscrowError::InvalidStatus
+    );
+
+    // ── Forfeit the agent's bond to requester ───────────────────────────
+    let bond_forfeited = job.bond_balance;
+    if bond_forfeited > 0 {
+        let seeds = job.escrow_authority_seeds();
+        let signer_seeds = &[&seeds[..]];
+
+        let bond = Escrow::new(
+            job.asset,
+            &ctx.accounts.token_program,
+            &ctx.accounts.bond_token,
+            &ctx.accounts.mint,
+            ctx.accounts.escrow_authority.to_account_info(),
+            ctx.accounts.bond_vault.as_ref().map(|v| v.to_account_info()),
+            ctx.remaining_accounts,
+        )?;
+        let requester = bond.destination(
+            ctx.accounts.requester_token.as_ref(),
+            ctx.accounts.requester.as_ref().map(|r| r.to_account_info()),
+        )?;
+        bond.pay(requester, bond_forfeited, signer_seeds)?;
+    }
+
+    let agent = job.agent;
+    job.bond_balance = 0;
+    job.agent = Pubkey::default();
+    job.status = JobStatus::Open;
+    job.deliverable = Deliverable::default();
+    job.artifact_count = 0;
+    job.revisions = 0;
+    job.submitted_at = 0;
+    job.clear_proposals();
+
+    // ── Return the artifacts record's rent to the agent ─────────────────
+    let agent_info = ctx.accounts.agent.to_account_info();
+    close_artifacts(&ctx.accounts.artifacts.to_account_info(), Some(&agent_info))?;
+
+    ctx.accounts.agent_profile.record_abandonment()?;
+
+    emit!(JobAbandoned {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        agent,
+        bond_forfeited,
+    });
+
+    msg!(
+        "Job {} abandoned by agent {} - reopened, {} bond forfeited",
+        job.job_id,
+        agent,
+        bond_forfeited
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AbandonJob<'info> {
+    /// SECURITY: PDA seeds + has_one = agent
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+        has_one = agent @ EscrowError::Unauthorized,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// CHECK: PDA authority. Validated by seeds.
+    #[account(
+        seeds = [b"escrow", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.escrow_authority_bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// SECURITY: Agent's reputation profile, validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"agent_profile", agent.key().as_ref()],
+        bump = agent_profile.bump,
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    /// CHECK: The job's DeliverableArtifacts PDA, validated by seeds. It only
+    /// exists if the agent submitted artifacts, so it is closed in the
+    /// handler when present; rent goes back to the agent who paid it.
+    #[account(
+        mut,
+        seeds = [b"artifacts", job.key().as_ref()],
+        bump,
+    )]
+    pub artifacts: UncheckedAccount<'info>,
+
+    // ── Bonded token jobs ───────────────────────────────────────────────
+
+    /// SECURITY: Bond escrow validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+        constraint = bond_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub bond_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Requester token owner + mint validated against job record
+    #[account(
+        mut,
+        constraint = requester_token.owner == job.requester @ EscrowError::InvalidTokenOwner,
+        constraint = requester_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub requester_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Job mint, required by transfer_checked
+    #[account(
+        address = job.mint @ EscrowError::InvalidMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // ── Bonded native SOL jobs ──────────────────────────────────────────
+
+    /// SECURITY: Bond vault validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+    )]
+    pub bond_vault: Option<Account<'info, SolVault>>,
+
+    /// CHECK: Receives the forfeited bond. Validated against job.requester.
+    #[account(
+        mut,
+        address = job.requester @ EscrowError::InvalidRecipient,
+    )]
+    pub requester: Option<UncheckedAccount<'info>>,
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::DeadlineExtended;
+
+/// The other party accepts a pending deadline extension.
+///
+/// `new_deadline` must match the pending proposal, so a proposer cannot swap
+/// in a different deadline while the acceptance is in flight. The job
+/// deadline moves to the proposed one and every milestone deadline is pushed
+/// back by the same amount.
+///
+/// SECURITY:
+/// - PDA seed validation on job account
+/// - Only the requester or agent who did NOT propose can accept
+pub fn handler(ctx: Context<AcceptDeadlineExtension>, new_deadline: i64) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    let party = ctx.accounts.party.key();
+
+    require!(
+        matches!(job.status, JobStatus::InProgress | JobStatus::UnderReview),
+        EscrowError::InvalidStatus
+    );
+    require!(
+        job.extension_proposer != Pubkey::default() && job.proposed_deadline == new_deadline,
+        EscrowError::NoExtensionProposed
+    );
+    require!(job.extension_proposer != party, EscrowError::Unauthorized);
+    require!(
+        job.extensions < MAX_DEADLINE_EXTENSIONS,
+        EscrowError::ExtensionLimitReached
+    );
+
+    let old_deadline = job.deadline;
+    job.extend_deadline(new_deadline)?;
+    job.extensions = job.extensions.checked_add(1).ok_or(EscrowError::Overflow)?;
+    job.extension_proposer = Pubkey::default();
+    job.proposed_deadline = 0;
+
+    emit!(DeadlineExtended {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        accepted_by: party,
+        old_deadline,
+        new_deadline,
+        extensions: job.extensions,
+    });
+
+    msg!(
+        "Job {} deadline extended: {} -> {} ({}/{})",
+        job.job_id,
+        old_deadline,
+        new_deadline,
+        job.extensions,
+        MAX_DEADLINE_EXTENSIONS
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptDeadlineExtension<'info> {
+    /// SECURITY: PDA seeds ensure legitimate job account
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// SECURITY: Must be the job's requester or assigned agent
+    #[account(
+        constraint = party.key() == job.requester || party.key() == job.agent
+            @ EscrowError::Unauthorized,
+    )]
+    pub party: Signer<'info>,
+}
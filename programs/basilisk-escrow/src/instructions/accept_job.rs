@@ -1,28 +1,124 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::errors::EscrowError;
+use crate::utils::transfer_tokens;
+use crate::events::JobAccepted;
 
 /// Agent accepts an open job.
 ///
 /// SECURITY FIX: Added PDA seed validation on job account to prevent
 /// passing arbitrary accounts. Status and assignment checks enforce
-/// that only open, unassigned jobs can be accepted.
-pub fn handler(ctx: Context<AcceptJob>) -> Result<()> {
+/// that only open, unassigned jobs can be accepted. Private jobs can only
+/// be accepted by one of their invited agents, and the requester can never
+/// accept their own job.
+///
+/// If the job requires a bond, the agent deposits it into the job's bond
+/// escrow here (`bond_token` + `agent_token` for token jobs, `bond_vault`
+/// for native SOL jobs). A percentage bond is taken of the amount escrowed
+/// at this point. An agent reserved through select_agent on a bonded job
+/// calls this to post the bond and start work.
+///
+/// A job can only be accepted before its current deadline: an expired job
+/// could be cancelled straight away, forfeiting the bond just posted.
+///
+/// The agent's AgentProfile is created here on their first acceptance, paid
+/// for by the agent.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, AcceptJob<'info>>) -> Result<()> {
     let job = &mut ctx.accounts.job;
+    let agent = ctx.accounts.agent.key();
 
     require!(job.status == JobStatus::Open, EscrowError::JobNotOpen);
     require!(
-        job.agent == Pubkey::default(),
+        job.agent == Pubkey::default() || job.agent == agent,
         EscrowError::JobAlreadyTaken
     );
+    require!(job.is_agent_allowed(&agent), EscrowError::AgentNotInvited);
+    require!(agent != job.requester, EscrowError::InvalidAgent);
+    require!(
+        Clock::get()?.unix_timestamp <= job.current_deadline(),
+        EscrowError::DeadlineExpired
+    );
+
+    // ── Deposit the agent's bond ────────────────────────────────────────
+    job.bond_amount = job.required_bond()?;
+    if job.bond_amount > 0 {
+        job.bond_balance = match job.asset {
+            AssetKind::SplToken => {
+                let token_program = ctx
+                    .accounts
+                    .token_program
+                    .as_ref()
+                    .ok_or(EscrowError::MissingAccount)?;
+                let mint = ctx.accounts.mint.as_ref().ok_or(EscrowError::MissingAccount)?;
+                let agent_token = ctx
+                    .accounts
+                    .agent_token
+                    .as_ref()
+                    .ok_or(EscrowError::MissingAccount)?;
+                let bond_token = ctx
+                    .accounts
+                    .bond_token
+                    .as_mut()
+                    .ok_or(EscrowError::MissingAccount)?;
+
+                transfer_tokens(
+                    token_program,
+                    agent_token.to_account_info(),
+                    mint,
+                    bond_token.to_account_info(),
+                    ctx.accounts.agent.to_account_info(),
+                    ctx.remaining_accounts,
+                    job.bond_amount,
+                    &[],
+                )?;
+
+                // Net of Token-2022 transfer fees
+                bond_token.reload()?;
+                bond_token.amount
+            }
+            AssetKind::NativeSol => {
+                let bond_vault = ctx
+                    .accounts
+                    .bond_vault
+                    .as_ref()
+                    .ok_or(EscrowError::MissingAccount)?;
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.agent.to_account_info(),
+                        to: bond_vault.to_account_info(),
+                    },
+                );
+                system_program::transfer(cpi_ctx, job.bond_amount)?;
+                job.bond_amount
+            }
+        };
+    }
 
-    job.agent = ctx.accounts.agent.key();
+    job.agent = agent;
     job.status = JobStatus::InProgress;
 
+    // ── Create the agent's reputation profile on first acceptance ───────
+    let profile = &mut ctx.accounts.agent_profile;
+    if profile.agent == Pubkey::default() {
+        profile.agent = agent;
+        profile.bump = ctx.bumps.agent_profile;
+    }
+
+    emit!(JobAccepted {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        agent: job.agent,
+        bond: job.bond_balance,
+    });
+
     msg!(
-        "Job {} accepted by agent {}",
+        "Job {} accepted by agent {} (bond: {})",
         job.job_id,
-        job.agent
+        job.agent,
+        job.bond_balance
     );
     Ok(())
 }
@@ -33,10 +129,61 @@ pub struct AcceptJob<'info> {
     /// created by this program, not an arbitrary account.
     #[account(
         mut,
-        seeds = [b"job", job.job_id.as_bytes()],
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
         bump = job.bump,
     )]
     pub job: Account<'info, Job>,
 
+    #[account(mut)]
     pub agent: Signer<'info>,
+
+    /// Agent's reputation profile, created the first time they take a job
+    #[account(
+        init_if_needed,
+        payer = agent,
+        space = 8 + AgentProfile::LEN,
+        seeds = [b"agent_profile", agent.key().as_ref()],
+        bump,
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    pub system_program: Program<'info, System>,
+
+    // ── Bonded token jobs ───────────────────────────────────────────────
+
+    /// SECURITY: Bond escrow validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+        constraint = bond_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub bond_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Validates owner is the signing agent AND mint matches
+    #[account(
+        mut,
+        constraint = agent_token.owner == agent.key() @ EscrowError::InvalidTokenOwner,
+        constraint = agent_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub agent_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Job mint, required by transfer_checked
+    #[account(
+        address = job.mint @ EscrowError::InvalidMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // ── Bonded native SOL jobs ──────────────────────────────────────────
+
+    /// SECURITY: Bond vault validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+    )]
+    pub bond_vault: Option<Account<'info, SolVault>>,
 }
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::utils::close_dispute_vote;
+use crate::events::JobSettled;
+use super::payouts::*;
+
+/// The other party accepts a pending settlement, splitting the escrow not yet
+/// released by agreement and moving the job to Resolved. On a Disputed job
+/// this settles every unreleased milestone, not just the disputed one.
+///
+/// `agent_percentage` and `bond_agent_percentage` must match the pending
+/// proposal, so a proposer cannot swap in a different split while the
+/// acceptance is in flight. The split is paid through SplitPayout like
+/// resolve_dispute without an arbitrator: no arbitration fee, the protocol
+/// fee on the agent's share, and the bond split separately. Payouts are
+/// added to the agent's AgentProfile volume; a settlement counts as neither
+/// a dispute won nor lost.
+///
+/// If the job was disputed, any partial arbitrator votes are discarded (the
+/// vote record is closed to `vote_payer`, the arbitrator who cast the first
+/// vote) and any Evidence records are closed back to their parties.
+///
+/// Token jobs pass the token accounts; native SOL jobs pass `sol_vault`,
+/// `agent`, `requester` and `sol_treasury` instead.
+///
+/// SECURITY:
+/// - Only the requester or agent who did NOT propose can accept
+/// - Job, escrow and payee accounts validated by SplitPayout
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AcceptSettlement<'info>>,
+    agent_percentage: u8,
+    bond_agent_percentage: u8,
+) -> Result<()> {
+    let job = &ctx.accounts.payout.job;
+    let party = ctx.accounts.party.key();
+
+    require!(
+        matches!(
+            job.status,
+            JobStatus::InProgress | JobStatus::UnderReview | JobStatus::Disputed
+        ),
+        EscrowError::InvalidStatus
+    );
+    require!(
+        job.settlement_proposer != Pubkey::default()
+            && job.settlement_agent_percentage == agent_percentage
+            && job.settlement_bond_agent_percentage == bond_agent_percentage,
+        EscrowError::NoSettlementProposed
+    );
+    require!(job.settlement_proposer != party, EscrowError::Unauthorized);
+
+    let split = job.split_remaining(agent_percentage, bond_agent_percentage)?;
+
+    ctx.accounts.payout.pay_split(&split, ctx.remaining_accounts)?;
+
+    // ── Discard any partial dispute vote ────────────────────────────────
+    let vote_payer = ctx.accounts.vote_payer.as_ref().map(|p| p.to_account_info());
+    close_dispute_vote(&ctx.accounts.dispute_vote.to_account_info(), vote_payer.as_ref())?;
+
+    let job = &ctx.accounts.payout.job;
+    emit!(JobSettled {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        accepted_by: party,
+        agent_amount: split.agent_payout,
+        requester_amount: split.requester_amount,
+        fee: split.fee,
+        agent_percentage,
+        bond_agent_amount: split.bond_agent_amount,
+        bond_requester_amount: split.bond_requester_amount,
+    });
+
+    msg!(
+        "Job {} settled by agreement: {}% ({}) to agent, {}% ({}) to requester, {} fee",
+        job.job_id,
+        agent_percentage,
+        split.agent_payout,
+        100 - agent_percentage,
+        split.requester_amount,
+        split.fee
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptSettlement<'info> {
+    pub payout: SplitPayout<'info>,
+
+    /// SECURITY: Must be the job's requester or assigned agent
+    #[account(
+        constraint = party.key() == payout.job.requester || party.key() == payout.job.agent
+            @ EscrowError::Unauthorized,
+    )]
+    pub party: Signer<'info>,
+
+    /// CHECK: The job's DisputeVote PDA, validated by seeds. It only exists
+    /// if the job is disputed and some arbitrator voted, so it is closed in
+    /// the handler when present.
+    #[account(
+        mut,
+        seeds = [b"dispute_vote", payout.job.key().as_ref()],
+        bump,
+    )]
+    pub dispute_vote: UncheckedAccount<'info>,
+
+    /// CHECK: Arbitrator who paid the vote record's rent, validated against
+    /// the record. Required only when the record exists.
+    #[account(mut)]
+    pub vote_payer: Option<UncheckedAccount<'info>>,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::ApplicationSubmitted;
+
+/// Agent applies to an open job with a proposed price, ETA and pitch hash.
+///
+/// Each agent gets one Application PDA per job and pays its rent, which is
+/// returned when the application is closed by select_agent or
+/// withdraw_application. The proposed price is informational; the escrowed
+/// amount is not renegotiated on-chain.
+///
+/// SECURITY:
+/// - PDA seed validation on job; application seeded by job + agent
+/// - Only Open, unassigned jobs accept applications
+/// - Private jobs only accept applications from invited agents
+pub fn handler(
+    ctx: Context<ApplyToJob>,
+    proposed_amount: u64,
+    eta_days: u8,
+    pitch_hash: [u8; 32],
+) -> Result<()> {
+    let job = &ctx.accounts.job;
+    let agent = ctx.accounts.agent.key();
+
+    require!(job.status == JobStatus::Open, EscrowError::JobNotOpen);
+    require!(
+        job.agent == Pubkey::default(),
+        EscrowError::JobAlreadyTaken
+    );
+    require!(job.is_agent_allowed(&agent), EscrowError::AgentNotInvited);
+    require!(agent != job.requester, EscrowError::InvalidAgent);
+
+    let application = &mut ctx.accounts.application;
+    application.job = job.key();
+    application.job_created_at = job.created_at;
+    application.agent = agent;
+    application.proposed_amount = proposed_amount;
+    application.eta_days = eta_days;
+    application.pitch_hash = pitch_hash;
+    application.created_at = Clock::get()?.unix_timestamp;
+    application.bump = ctx.bumps.application;
+
+    emit!(ApplicationSubmitted {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        agent,
+        proposed_amount,
+        eta_days,
+    });
+
+    msg!("Agent {} applied to job {}", agent, job.job_id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApplyToJob<'info> {
+    /// SECURITY: PDA seed constraint ensures this is a legitimate job account
+    #[account(
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+    )]
+    pub job: Account<'info, Job>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + Application::LEN,
+        seeds = [b"application", job.key().as_ref(), agent.key().as_ref()],
+        bump,
+    )]
+    pub application: Account<'info, Application>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
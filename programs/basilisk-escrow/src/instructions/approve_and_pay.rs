@@ -1,53 +1,65 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::*;
 use crate::errors::EscrowError;
+use crate::events::JobApproved;
+use super::payouts::*;
 
-/// Requester approves work and releases escrowed payment to agent.
+/// Requester approves the current milestone and releases its share of the
+/// escrow to the agent. The job's protocol fee is routed to the treasury and
+/// the rest is paid to the agent. The job is Completed once the final
+/// milestone is paid; otherwise it returns to InProgress for the next one.
+/// Any bond the agent posted is returned with the final payment.
+///
+/// The job is rated once, on the final milestone: `rating` must be 1-5 when
+/// approving the final milestone and 0 for every earlier one. Every payout
+/// adds to the agent's AgentProfile volume for the job's mint; the final
+/// approval also counts the job as completed with its rating.
+///
+/// The payout itself is shared with claim_after_review_timeout through
+/// MilestonePayout, which carries the job, escrow and payee accounts.
 ///
 /// SECURITY FIXES:
-/// - PDA seed validation on job account
-/// - has_one = requester ensures only the job poster can approve
-/// - Escrow token validated by PDA seeds (cannot substitute fake account)
-/// - Agent token owner validated against job.agent
-/// - Mint consistency validated across all token accounts
-pub fn handler(ctx: Context<ApproveAndPay>, rating: u8) -> Result<()> {
-    let job = &mut ctx.accounts.job;
+/// - requester must match job.requester, so only the job poster can approve
+/// - Job, escrow and payee accounts validated by MilestonePayout
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ApproveAndPay<'info>>,
+    milestone_index: u8,
+    rating: u8,
+) -> Result<()> {
+    if ctx.accounts.payout.job.is_last_milestone() {
+        require!((1..=5).contains(&rating), EscrowError::InvalidRating);
+    } else {
+        require!(rating == 0, EscrowError::InvalidRating);
+    }
 
-    require!(
-        job.status == JobStatus::UnderReview,
-        EscrowError::InvalidStatus
-    );
-    require!(rating >= 1 && rating <= 5, EscrowError::InvalidRating);
+    let payment = ctx
+        .accounts
+        .payout
+        .pay(milestone_index, Some(rating), ctx.remaining_accounts)?;
 
-    // ── Transfer from escrow to agent ───────────────────────────────────
-    let job_id_bytes = job.job_id.as_bytes();
-    let seeds: &[&[u8]] = &[
-        b"escrow",
-        job_id_bytes,
-        &[job.escrow_authority_bump],
-    ];
-    let signer_seeds = &[seeds];
-
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.escrow_token.to_account_info(),
-        to: ctx.accounts.agent_token.to_account_info(),
-        authority: ctx.accounts.escrow_authority.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer_seeds,
-    );
-    token::transfer(cpi_ctx, job.amount)?;
+    let job = &mut ctx.accounts.payout.job;
+    if payment.completed {
+        job.rating = rating;
+    }
 
-    job.status = JobStatus::Completed;
-    job.rating = rating;
+    emit!(JobApproved {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        agent: job.agent,
+        milestone: milestone_index,
+        amount: payment.amount,
+        fee: payment.fee,
+        rating,
+        completed: payment.completed,
+        bond_returned: payment.bond_returned,
+        auto_approved: false,
+    });
 
     msg!(
-        "Job {} approved - {} tokens paid to agent (rating: {})",
+        "Job {} milestone {} approved - {} paid to agent, {} fee (rating: {})",
         job.job_id,
-        job.amount,
+        milestone_index,
+        payment.amount,
+        payment.fee,
         rating
     );
     Ok(())
@@ -55,40 +67,9 @@ pub fn handler(ctx: Context<ApproveAndPay>, rating: u8) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct ApproveAndPay<'info> {
-    /// SECURITY: PDA seeds + has_one = requester
-    #[account(
-        mut,
-        seeds = [b"job", job.job_id.as_bytes()],
-        bump = job.bump,
-        has_one = requester @ EscrowError::Unauthorized,
-    )]
-    pub job: Account<'info, Job>,
-
-    /// CHECK: PDA authority for escrow. Validated by seeds constraint.
-    #[account(
-        seeds = [b"escrow", job.job_id.as_bytes()],
-        bump = job.escrow_authority_bump,
-    )]
-    pub escrow_authority: UncheckedAccount<'info>,
+    pub payout: MilestonePayout<'info>,
 
+    /// SECURITY: Only the job's requester can approve
+    #[account(address = payout.job.requester @ EscrowError::Unauthorized)]
     pub requester: Signer<'info>,
-
-    /// SECURITY: Escrow token validated by PDA seeds — cannot be substituted
-    #[account(
-        mut,
-        seeds = [b"escrow_token", job.job_id.as_bytes()],
-        bump = job.escrow_token_bump,
-        constraint = escrow_token.mint == job.mint @ EscrowError::InvalidMint,
-    )]
-    pub escrow_token: Account<'info, TokenAccount>,
-
-    /// SECURITY: Validates owner is the assigned agent AND mint matches
-    #[account(
-        mut,
-        constraint = agent_token.owner == job.agent @ EscrowError::InvalidTokenOwner,
-        constraint = agent_token.mint == job.mint @ EscrowError::InvalidMint,
-    )]
-    pub agent_token: Account<'info, TokenAccount>,
-
-    pub token_program: Program<'info, Token>,
 }
@@ -1,51 +1,84 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::errors::EscrowError;
+use crate::utils::Escrow;
+use crate::events::JobCancelled;
 
-/// Requester cancels a job before any agent accepts.
-/// Escrowed funds are returned to requester.
+/// Requester cancels a job before any agent accepts, or once the current
+/// milestone's deadline has passed. Escrow not yet released for approved
+/// milestones is returned to requester. If the agent let the job expire,
+/// their bond is forfeited to the requester as well.
+///
+/// Token jobs pass the token accounts; native SOL jobs pass `sol_vault`
+/// instead and are refunded straight to the requester's wallet.
 ///
 /// SECURITY FIXES:
 /// - PDA seed validation on job account
 /// - has_one = requester
-/// - Escrow token validated by PDA seeds
+/// - Escrow token / SOL vault validated by PDA seeds
 /// - Requester token owner + mint validated
-pub fn handler(ctx: Context<CancelJob>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelJob<'info>>) -> Result<()> {
     let job = &mut ctx.accounts.job;
 
     let clock = Clock::get()?;
     let is_open = job.status == JobStatus::Open;
-    let is_expired = job.status == JobStatus::InProgress && clock.unix_timestamp > job.deadline;
+    let is_expired =
+        job.status == JobStatus::InProgress && clock.unix_timestamp > job.current_deadline();
     require!(is_open || is_expired, EscrowError::CannotCancel);
 
+    let refund_amount = job.remaining_amount()?;
+
     // ── Refund to requester ─────────────────────────────────────────────
-    let job_id_bytes = job.job_id.as_bytes();
-    let seeds: &[&[u8]] = &[
-        b"escrow",
-        job_id_bytes,
-        &[job.escrow_authority_bump],
-    ];
-    let signer_seeds = &[seeds];
-
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.escrow_token.to_account_info(),
-        to: ctx.accounts.requester_token.to_account_info(),
-        authority: ctx.accounts.escrow_authority.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer_seeds,
-    );
-    token::transfer(cpi_ctx, job.amount)?;
+    let seeds = job.escrow_authority_seeds();
+    let signer_seeds = &[&seeds[..]];
 
+    let escrow = Escrow::new(
+        job.asset,
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_token,
+        &ctx.accounts.mint,
+        ctx.accounts.escrow_authority.to_account_info(),
+        ctx.accounts.sol_vault.as_ref().map(|v| v.to_account_info()),
+        ctx.remaining_accounts,
+    )?;
+    let requester = escrow.destination(
+        ctx.accounts.requester_token.as_ref(),
+        Some(ctx.accounts.requester.to_account_info()),
+    )?;
+    escrow.pay(requester.clone(), refund_amount, signer_seeds)?;
+
+    // ── Forfeit the agent's bond to requester ───────────────────────────
+    let bond_forfeited = job.bond_balance;
+    if bond_forfeited > 0 {
+        let bond = Escrow::new(
+            job.asset,
+            &ctx.accounts.token_program,
+            &ctx.accounts.bond_token,
+            &ctx.accounts.mint,
+            ctx.accounts.escrow_authority.to_account_info(),
+            ctx.accounts.bond_vault.as_ref().map(|v| v.to_account_info()),
+            ctx.remaining_accounts,
+        )?;
+        bond.pay(requester, bond_forfeited, signer_seeds)?;
+    }
+
+    job.bond_balance = 0;
     job.status = JobStatus::Cancelled;
+    job.clear_proposals();
+
+    emit!(JobCancelled {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        refund_amount,
+        bond_forfeited,
+    });
 
     msg!(
-        "Job {} cancelled - {} tokens returned to requester",
+        "Job {} cancelled - {} returned to requester, {} bond forfeited",
         job.job_id,
-        job.amount
+        refund_amount,
+        bond_forfeited
     );
     Ok(())
 }
@@ -55,7 +88,7 @@ pub struct CancelJob<'info> {
     /// SECURITY: PDA seeds + has_one = requester
     #[account(
         mut,
-        seeds = [b"job", job.job_id.as_bytes()],
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
         bump = job.bump,
         has_one = requester @ EscrowError::Unauthorized,
     )]
@@ -63,21 +96,24 @@ pub struct CancelJob<'info> {
 
     /// CHECK: PDA authority. Validated by seeds.
     #[account(
-        seeds = [b"escrow", job.job_id.as_bytes()],
+        seeds = [b"escrow", job.requester.as_ref(), job.job_id_hash.as_ref()],
         bump = job.escrow_authority_bump,
     )]
     pub escrow_authority: UncheckedAccount<'info>,
 
+    #[account(mut)]
     pub requester: Signer<'info>,
 
+    // ── Token jobs ──────────────────────────────────────────────────────
+
     /// SECURITY: Escrow token validated by PDA seeds
     #[account(
         mut,
-        seeds = [b"escrow_token", job.job_id.as_bytes()],
+        seeds = [b"escrow_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
         bump = job.escrow_token_bump,
         constraint = escrow_token.mint == job.mint @ EscrowError::InvalidMint,
     )]
-    pub escrow_token: Account<'info, TokenAccount>,
+    pub escrow_token: Option<InterfaceAccount<'info, TokenAccount>>,
 
     /// SECURITY: Requester token owner + mint validated
     #[account(
@@ -85,7 +121,43 @@ pub struct CancelJob<'info> {
         constraint = requester_token.owner == requester.key() @ EscrowError::InvalidTokenOwner,
         constraint = requester_token.mint == job.mint @ EscrowError::InvalidMint,
     )]
-    pub requester_token: Account<'info, TokenAccount>,
+    pub requester_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Job mint, required by transfer_checked
+    #[account(
+        address = job.mint @ EscrowError::InvalidMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // ── Native SOL jobs ─────────────────────────────────────────────────
+
+    /// SECURITY: SOL vault validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"sol_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.vault_bump,
+    )]
+    pub sol_vault: Option<Account<'info, SolVault>>,
+
+    // ── Agent bond (bonded jobs only) ───────────────────────────────────
+
+    /// SECURITY: Bond escrow validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+        constraint = bond_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub bond_token: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    pub token_program: Program<'info, Token>,
+    /// SECURITY: Bond vault validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+    )]
+    pub bond_vault: Option<Account<'info, SolVault>>,
 }
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::errors::EscrowError;
+use crate::events::JobApproved;
+use super::payouts::*;
+
+/// Pays the agent for the current milestone once the requester has let the
+/// review window elapse without calling approve_and_pay or reject_work.
+///
+/// Permissionless: the agent or any cranker can submit it, since funds only
+/// ever move to the job's agent and treasury. Payment works exactly like an
+/// approval (protocol fee to the treasury, the rest to the agent, bond
+/// returned with the final milestone) but no rating is recorded; the agent's
+/// AgentProfile counts the job as completed without a rating.
+///
+/// The payout itself is shared with approve_and_pay through MilestonePayout.
+///
+/// SECURITY:
+/// - Only callable once job.submitted_at + job.review_period has passed
+/// - Job, escrow and payee accounts validated by MilestonePayout
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimAfterReviewTimeout<'info>>,
+    milestone_index: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp > ctx.accounts.payout.job.review_deadline()?,
+        EscrowError::ReviewWindowOpen
+    );
+
+    let payment = ctx
+        .accounts
+        .payout
+        .pay(milestone_index, None, ctx.remaining_accounts)?;
+
+    let job = &ctx.accounts.payout.job;
+
+    emit!(JobApproved {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        agent: job.agent,
+        milestone: milestone_index,
+        amount: payment.amount,
+        fee: payment.fee,
+        rating: 0,
+        completed: payment.completed,
+        bond_returned: payment.bond_returned,
+        auto_approved: true,
+    });
+
+    msg!(
+        "Job {} milestone {} auto-approved after review timeout - {} paid to agent, {} fee",
+        job.job_id,
+        milestone_index,
+        payment.amount,
+        payment.fee
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimAfterReviewTimeout<'info> {
+    pub payout: MilestonePayout<'info>,
+}
@@ -0,0 +1,290 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::utils::{close_artifacts, close_token_account, transfer_tokens};
+use crate::events::JobClosed;
+
+/// Requester closes a job that reached a terminal state.
+///
+/// Rent held by the escrow token account and the Job account is returned to
+/// the requester who paid for them in create_job. Any dust left in the escrow
+/// (e.g. tokens sent to it directly) is swept back to the requester first so
+/// the token account can be closed. If an `archive` account is supplied, a
+/// compact JobArchive record is written before the Job account is closed.
+///
+/// Native SOL jobs pass `sol_vault` instead of the token accounts; the vault
+/// is closed to the requester, returning its rent and any stray lamports.
+/// Bonded jobs also pass their `bond_token` / `bond_vault`, which is closed
+/// the same way; any bond still in it at this point was already settled.
+/// The job's `artifacts` PDA is always passed; if the agent submitted extra
+/// artifacts, the `agent` who paid for the record must be passed too and
+/// receives its rent.
+///
+/// A Completed or Resolved job can only be closed once its agent has rated
+/// the requester via rate_requester, or RATING_WINDOW_DAYS after it settled,
+/// so a requester cannot settle and close in one transaction to dodge a bad
+/// rating.
+///
+/// SECURITY:
+/// - PDA seed validation on job, escrow authority, escrow token, SOL vault
+///   and bond escrow
+/// - has_one = requester; lamports can only flow back to the job poster
+/// - Only terminal jobs (escrow already settled) can be closed
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CloseJob<'info>>) -> Result<()> {
+    let job = &ctx.accounts.job;
+
+    require!(job.status.is_terminal(), EscrowError::JobNotTerminal);
+    require!(
+        !job.awaiting_requester_rating(Clock::get()?.unix_timestamp)?,
+        EscrowError::RatingWindowOpen
+    );
+
+    match job.asset {
+        AssetKind::SplToken => {
+            let token_program = ctx
+                .accounts
+                .token_program
+                .as_ref()
+                .ok_or(EscrowError::MissingAccount)?;
+            let escrow_token = ctx
+                .accounts
+                .escrow_token
+                .as_ref()
+                .ok_or(EscrowError::MissingAccount)?;
+            let mint = ctx.accounts.mint.as_ref().ok_or(EscrowError::MissingAccount)?;
+
+            let seeds = job.escrow_authority_seeds();
+            let signer_seeds = &[&seeds[..]];
+
+            // ── Sweep any leftover balance back to requester ────────────
+            let leftover = escrow_token.amount;
+            if leftover > 0 {
+                let requester_token = ctx
+                    .accounts
+                    .requester_token
+                    .as_ref()
+                    .ok_or(EscrowError::MissingAccount)?;
+                transfer_tokens(
+                    token_program,
+                    escrow_token.to_account_info(),
+                    mint,
+                    requester_token.to_account_info(),
+                    ctx.accounts.escrow_authority.to_account_info(),
+                    ctx.remaining_accounts,
+                    leftover,
+                    signer_seeds,
+                )?;
+            }
+
+            // ── Close escrow token account ──────────────────────────────
+            close_token_account(
+                token_program,
+                escrow_token,
+                mint,
+                ctx.accounts.requester.to_account_info(),
+                ctx.accounts.escrow_authority.to_account_info(),
+                signer_seeds,
+            )?;
+
+            // ── Sweep and close the bond escrow (bonded jobs only) ──────
+            if job.is_bonded() {
+                let bond_token = ctx
+                    .accounts
+                    .bond_token
+                    .as_ref()
+                    .ok_or(EscrowError::MissingAccount)?;
+                if bond_token.amount > 0 {
+                    let requester_token = ctx
+                        .accounts
+                        .requester_token
+                        .as_ref()
+                        .ok_or(EscrowError::MissingAccount)?;
+                    transfer_tokens(
+                        token_program,
+                        bond_token.to_account_info(),
+                        mint,
+                        requester_token.to_account_info(),
+                        ctx.accounts.escrow_authority.to_account_info(),
+                        ctx.remaining_accounts,
+                        bond_token.amount,
+                        signer_seeds,
+                    )?;
+                }
+                close_token_account(
+                    token_program,
+                    bond_token,
+                    mint,
+                    ctx.accounts.requester.to_account_info(),
+                    ctx.accounts.escrow_authority.to_account_info(),
+                    signer_seeds,
+                )?;
+            }
+        }
+        AssetKind::NativeSol => {
+            // The vaults themselves are closed to the requester by the
+            // `close = requester` constraints on exit.
+            require!(
+                ctx.accounts.sol_vault.is_some(),
+                EscrowError::MissingAccount
+            );
+            require!(
+                !job.is_bonded() || ctx.accounts.bond_vault.is_some(),
+                EscrowError::MissingAccount
+            );
+        }
+    }
+
+    // ── Return the artifacts record's rent to the agent ─────────────────
+    let agent = ctx.accounts.agent.as_ref().map(|a| a.to_account_info());
+    close_artifacts(&ctx.accounts.artifacts.to_account_info(), agent.as_ref())?;
+
+    // ── Optional archival record ────────────────────────────────────────
+    let archived = if let (Some(archive), Some(bump)) =
+        (ctx.accounts.archive.as_mut(), ctx.bumps.archive)
+    {
+        archive.job = job.key();
+        archive.job_id = job.job_id.clone();
+        archive.requester = job.requester;
+        archive.agent = job.agent;
+        archive.mint = job.mint;
+        archive.amount = job.amount;
+        archive.status = job.status;
+        archive.rating = job.rating;
+        archive.requester_rating = job.requester_rating;
+        archive.created_at = job.created_at;
+        archive.closed_at = Clock::get()?.unix_timestamp;
+        archive.bump = bump;
+        true
+    } else {
+        false
+    };
+
+    emit!(JobClosed {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        archived,
+    });
+
+    msg!(
+        "Job {} closed - rent returned to requester (archived: {})",
+        job.job_id,
+        archived
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseJob<'info> {
+    /// SECURITY: PDA seeds + has_one = requester. Rent goes back to requester.
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+        has_one = requester @ EscrowError::Unauthorized,
+        close = requester,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// CHECK: PDA authority for escrow. Validated by seeds.
+    #[account(
+        seeds = [b"escrow", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.escrow_authority_bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    /// Optional compact record of the job, kept after the Job is closed.
+    /// Seeded with the job's created_at as well, so a job re-created at the
+    /// same address can be archived again.
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + JobArchive::LEN,
+        seeds = [b"archive", job.key().as_ref(), &job.created_at.to_le_bytes()],
+        bump,
+    )]
+    pub archive: Option<Account<'info, JobArchive>>,
+
+    // ── Token jobs ──────────────────────────────────────────────────────
+
+    /// SECURITY: Escrow token validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"escrow_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.escrow_token_bump,
+        constraint = escrow_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub escrow_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Requester token owner + mint validated
+    #[account(
+        mut,
+        constraint = requester_token.owner == requester.key() @ EscrowError::InvalidTokenOwner,
+        constraint = requester_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub requester_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Job mint, required by transfer_checked and
+    /// fee harvesting (Token-2022)
+    #[account(
+        mut,
+        address = job.mint @ EscrowError::InvalidMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // ── Native SOL jobs ─────────────────────────────────────────────────
+
+    /// SECURITY: SOL vault validated by PDA seeds. Rent goes back to requester.
+    #[account(
+        mut,
+        seeds = [b"sol_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.vault_bump,
+        close = requester,
+    )]
+    pub sol_vault: Option<Account<'info, SolVault>>,
+
+    // ── Agent bond (bonded jobs only) ───────────────────────────────────
+
+    /// SECURITY: Bond escrow validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+        constraint = bond_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub bond_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Bond vault validated by PDA seeds. Rent goes back to requester.
+    #[account(
+        mut,
+        seeds = [b"bond_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+        close = requester,
+    )]
+    pub bond_vault: Option<Account<'info, SolVault>>,
+
+    // ── Deliverable artifacts ───────────────────────────────────────────
+
+    /// CHECK: The job's DeliverableArtifacts PDA, validated by seeds. It only
+    /// exists if the agent submitted artifacts, so it is closed in the
+    /// handler when present.
+    #[account(
+        mut,
+        seeds = [b"artifacts", job.key().as_ref()],
+        bump,
+    )]
+    pub artifacts: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the artifacts record's rent. Validated against
+    /// artifacts.agent in the handler.
+    #[account(mut)]
+    pub agent: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
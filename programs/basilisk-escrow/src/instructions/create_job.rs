@@ -1,67 +1,105 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::errors::EscrowError;
+use crate::utils::transfer_tokens;
+use crate::events::JobCreated;
 
 /// Create a new escrow job.
 ///
 /// Requester posts a job with funds locked in a PDA-controlled escrow account.
 /// The escrow token account is initialized as a PDA so only the program can
-/// authorize transfers out of it.
-pub fn handler(
-    ctx: Context<CreateJob>,
+/// authorize transfers out of it. All per-job PDAs are seeded with the
+/// requester's pubkey, so a job_id cannot be claimed by someone else first.
+/// The current protocol fee is snapshotted onto the job, and the mint's
+/// treasury account is created on first use so that payouts can always
+/// route the fee.
+///
+/// Both SPL Token and Token-2022 mints are supported. For Token-2022 mints
+/// with a transfer fee, the job escrows the net amount actually received;
+/// transfer-hook accounts are passed through `remaining_accounts`.
+///
+/// The escrow can optionally be split into up to MAX_MILESTONES milestones
+/// that are submitted, approved and paid one at a time. Milestone amounts
+/// must sum to `amount`; with no milestones the job has a single implicit
+/// milestone for the full amount.
+///
+/// Passing `terms.allowed_agents` makes the job private: only the invited
+/// agents (up to MAX_ALLOWED_AGENTS) can accept it.
+///
+/// A non-zero `terms.bond_amount` requires the agent to deposit that many
+/// tokens on acceptance; `terms.bond_bps` instead requires that share of
+/// the escrowed amount at the time of acceptance. The `bond_token` escrow
+/// must then be supplied so it can be created here alongside the job escrow.
+///
+/// `terms.arbitrator` names a specialist arbitrator who alone decides the
+/// job's disputes in place of the config panel. If the admin restricts job
+/// arbitrators to the registry, their `arbitrator_registration` must be
+/// supplied.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateJob<'info>>,
     job_id: String,
     amount: u64,
     description: String,
     deadline_days: u8,
+    terms: JobTerms,
 ) -> Result<()> {
-    // ── Input validation ────────────────────────────────────────────────
-    require!(job_id.len() <= MAX_JOB_ID_LEN, EscrowError::JobIdTooLong);
-    require!(
-        description.len() <= MAX_DESCRIPTION_LEN,
-        EscrowError::DescriptionTooLong
-    );
-    require!(amount > 0, EscrowError::ZeroAmount);
-
     // ── Initialize job state ────────────────────────────────────────────
     let job = &mut ctx.accounts.job;
-    let clock = Clock::get()?;
-
-    job.job_id = job_id;
-    job.requester = ctx.accounts.requester.key();
-    job.agent = Pubkey::default(); // Not assigned yet
-    job.amount = amount;
-    job.description = description;
-    job.status = JobStatus::Open;
-    job.created_at = clock.unix_timestamp;
-    job.deadline = clock
-        .unix_timestamp
-        .checked_add((deadline_days as i64).checked_mul(86400).ok_or(EscrowError::Overflow)?)
-        .ok_or(EscrowError::Overflow)?;
-    job.deliverable = String::new();
-    job.disputed = false;
-    job.rating = 0;
+    job.open(
+        job_id,
+        ctx.accounts.requester.key(),
+        amount,
+        description,
+        deadline_days,
+        terms,
+        &ctx.accounts.config,
+    )?;
+    ctx.accounts.config.check_job_arbitrator(
+        &job.arbitrator,
+        ctx.accounts.arbitrator_registration.as_deref(),
+    )?;
     job.bump = ctx.bumps.job;
     job.escrow_authority_bump = ctx.bumps.escrow_authority;
     job.escrow_token_bump = ctx.bumps.escrow_token;
     job.mint = ctx.accounts.mint.key();
+    job.asset = AssetKind::SplToken;
+    if job.is_bonded() {
+        job.bond_bump = ctx.bumps.bond_token.ok_or(EscrowError::MissingAccount)?;
+    }
+    job.vault_bump = 0;
 
     // ── Transfer tokens to escrow ───────────────────────────────────────
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.requester_token.to_account_info(),
-        to: ctx.accounts.escrow_token.to_account_info(),
-        authority: ctx.accounts.requester.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-    );
-    token::transfer(cpi_ctx, amount)?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        ctx.accounts.requester_token.to_account_info(),
+        &ctx.accounts.mint,
+        ctx.accounts.escrow_token.to_account_info(),
+        ctx.accounts.requester.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        &[],
+    )?;
+
+    // ── Record the net amount received (Token-2022 transfer fees) ───────
+    ctx.accounts.escrow_token.reload()?;
+    let received = ctx.accounts.escrow_token.amount;
+    require!(received > 0, EscrowError::ZeroAmount);
+    job.set_received_amount(received)?;
+
+    emit!(JobCreated {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        requester: job.requester,
+        mint: job.mint,
+        amount: received,
+        deadline: job.deadline,
+    });
 
     msg!(
         "Job created: {} - {} tokens escrowed (mint: {})",
         job.job_id,
-        amount,
+        received,
         job.mint
     );
     Ok(())
@@ -75,7 +113,7 @@ pub struct CreateJob<'info> {
         init,
         payer = requester,
         space = 8 + Job::LEN,
-        seeds = [b"job", job_id.as_bytes()],
+        seeds = [b"job", requester.key().as_ref(), job_id_hash(&job_id).as_ref()],
         bump,
     )]
     pub job: Account<'info, Job>,
@@ -83,7 +121,7 @@ pub struct CreateJob<'info> {
     // ── Escrow authority PDA (signs token transfers) ────────────────────
     /// CHECK: PDA authority for escrow token account. Validated by seeds.
     #[account(
-        seeds = [b"escrow", job_id.as_bytes()],
+        seeds = [b"escrow", requester.key().as_ref(), job_id_hash(&job_id).as_ref()],
         bump,
     )]
     pub escrow_authority: UncheckedAccount<'info>,
@@ -94,10 +132,50 @@ pub struct CreateJob<'info> {
         payer = requester,
         token::mint = mint,
         token::authority = escrow_authority,
-        seeds = [b"escrow_token", job_id.as_bytes()],
+        token::token_program = token_program,
+        seeds = [b"escrow_token", requester.key().as_ref(), job_id_hash(&job_id).as_ref()],
+        bump,
+    )]
+    pub escrow_token: InterfaceAccount<'info, TokenAccount>,
+
+    // ── Agent bond token account PDA (bonded jobs only) ─────────────────
+    #[account(
+        init,
+        payer = requester,
+        token::mint = mint,
+        token::authority = escrow_authority,
+        token::token_program = token_program,
+        seeds = [b"bond_token", requester.key().as_ref(), job_id_hash(&job_id).as_ref()],
+        bump,
+    )]
+    pub bond_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // ── Program config (fee source) ─────────────────────────────────────
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    // ── Registry entry for the job's arbitrator (if one is named) ───────
+    /// SECURITY: Registration validated by PDA seeds
+    #[account(
+        seeds = [b"arbitrator", arbitrator_registration.arbitrator.as_ref()],
+        bump = arbitrator_registration.bump,
+    )]
+    pub arbitrator_registration: Option<Account<'info, ArbitratorRegistration>>,
+
+    // ── Protocol fee treasury for this mint ─────────────────────────────
+    #[account(
+        init_if_needed,
+        payer = requester,
+        token::mint = mint,
+        token::authority = config,
+        token::token_program = token_program,
+        seeds = [b"treasury", mint.key().as_ref()],
         bump,
     )]
-    pub escrow_token: Account<'info, TokenAccount>,
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
 
     // ── Requester (signer + payer) ──────────────────────────────────────
     #[account(mut)]
@@ -110,13 +188,14 @@ pub struct CreateJob<'info> {
         constraint = requester_token.owner == requester.key() @ EscrowError::InvalidTokenOwner,
         constraint = requester_token.mint == mint.key() @ EscrowError::InvalidMint,
     )]
-    pub requester_token: Account<'info, TokenAccount>,
+    pub requester_token: InterfaceAccount<'info, TokenAccount>,
 
-    // ── Token mint ──────────────────────────────────────────────────────
-    pub mint: Account<'info, Mint>,
+    // ── Token mint (SPL Token or Token-2022) ────────────────────────────
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     // ── Programs ────────────────────────────────────────────────────────
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::JobCreated;
+
+/// Create a new escrow job funded with native SOL.
+///
+/// Same lifecycle as create_job, but the requester's lamports are moved
+/// straight into a program-owned SOL vault PDA instead of an SPL token
+/// escrow, so no wrapped SOL or token accounts are involved. Payouts and
+/// refunds move lamports directly out of the vault. Milestones, invited
+/// agents and a named arbitrator work the same way as in create_job; a
+/// bonded job must supply the `bond_vault` that will hold the agent's
+/// lamport bond.
+pub fn handler(
+    ctx: Context<CreateSolJob>,
+    job_id: String,
+    amount: u64,
+    description: String,
+    deadline_days: u8,
+    terms: JobTerms,
+) -> Result<()> {
+    // ── Initialize job state ────────────────────────────────────────────
+    let job = &mut ctx.accounts.job;
+    job.open(
+        job_id,
+        ctx.accounts.requester.key(),
+        amount,
+        description,
+        deadline_days,
+        terms,
+        &ctx.accounts.config,
+    )?;
+    ctx.accounts.config.check_job_arbitrator(
+        &job.arbitrator,
+        ctx.accounts.arbitrator_registration.as_deref(),
+    )?;
+    job.bump = ctx.bumps.job;
+    job.escrow_authority_bump = ctx.bumps.escrow_authority;
+    job.escrow_token_bump = 0;
+    job.mint = Pubkey::default();
+    job.asset = AssetKind::NativeSol;
+    if job.is_bonded() {
+        job.bond_bump = ctx.bumps.bond_vault.ok_or(EscrowError::MissingAccount)?;
+    }
+    job.vault_bump = ctx.bumps.sol_vault;
+
+    // ── Transfer lamports to vault ──────────────────────────────────────
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.requester.to_account_info(),
+            to: ctx.accounts.sol_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_ctx, amount)?;
+
+    emit!(JobCreated {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        requester: job.requester,
+        mint: job.mint,
+        amount,
+        deadline: job.deadline,
+    });
+
+    msg!(
+        "Job created: {} - {} lamports escrowed",
+        job.job_id,
+        amount
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(job_id: String)]
+pub struct CreateSolJob<'info> {
+    // ── Job PDA ─────────────────────────────────────────────────────────
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + Job::LEN,
+        seeds = [b"job", requester.key().as_ref(), job_id_hash(&job_id).as_ref()],
+        bump,
+    )]
+    pub job: Account<'info, Job>,
+
+    // ── Escrow authority PDA ────────────────────────────────────────────
+    /// CHECK: PDA authority for the job's escrow. Validated by seeds.
+    #[account(
+        seeds = [b"escrow", requester.key().as_ref(), job_id_hash(&job_id).as_ref()],
+        bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    // ── SOL vault PDA (holds escrowed lamports) ─────────────────────────
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + SolVault::LEN,
+        seeds = [b"sol_vault", requester.key().as_ref(), job_id_hash(&job_id).as_ref()],
+        bump,
+    )]
+    pub sol_vault: Account<'info, SolVault>,
+
+    // ── Agent bond vault PDA (bonded jobs only) ─────────────────────────
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + SolVault::LEN,
+        seeds = [b"bond_vault", requester.key().as_ref(), job_id_hash(&job_id).as_ref()],
+        bump,
+    )]
+    pub bond_vault: Option<Account<'info, SolVault>>,
+
+    // ── Program config (fee source) ─────────────────────────────────────
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    // ── Registry entry for the job's arbitrator (if one is named) ───────
+    /// SECURITY: Registration validated by PDA seeds
+    #[account(
+        seeds = [b"arbitrator", arbitrator_registration.arbitrator.as_ref()],
+        bump = arbitrator_registration.bump,
+    )]
+    pub arbitrator_registration: Option<Account<'info, ArbitratorRegistration>>,
+
+    // ── Protocol fee treasury for native SOL ────────────────────────────
+    #[account(
+        init_if_needed,
+        payer = requester,
+        space = 8 + SolTreasury::LEN,
+        seeds = [b"sol_treasury"],
+        bump,
+    )]
+    pub sol_treasury: Account<'info, SolTreasury>,
+
+    // ── Requester (signer + payer) ──────────────────────────────────────
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::{ArbitratorRegistration, ProgramConfig};
+use crate::errors::EscrowError;
+use crate::events::ArbitratorRegistered;
+
+/// Remove an arbitrator from the registry (admin-only), returning the rent
+/// to the admin. Jobs that already named the arbitrator keep them.
+pub fn handler(ctx: Context<DeregisterArbitrator>) -> Result<()> {
+    let arbitrator = ctx.accounts.registration.arbitrator;
+
+    emit!(ArbitratorRegistered {
+        arbitrator,
+        registered: false,
+    });
+
+    msg!("Arbitrator deregistered: {}", arbitrator);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DeregisterArbitrator<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// SECURITY: Registration validated by PDA seeds. Rent goes back to admin.
+    #[account(
+        mut,
+        seeds = [b"arbitrator", registration.arbitrator.as_ref()],
+        bump = registration.bump,
+        close = admin,
+    )]
+    pub registration: Account<'info, ArbitratorRegistration>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::utils::close_dispute_vote;
+use crate::events::DisputeExpired;
+use super::payouts::*;
+
+/// Settle a dispute that no arbitrator resolved before its deadline.
+///
+/// Permissionless: once `job.dispute_deadline` (set by reject_work from the
+/// config's dispute window) has passed, anyone can apply the fallback split
+/// from ProgramConfig (50/50 by default) to the disputed milestone, so an
+/// inactive arbitrator cannot lock the escrow forever. Like resolve_dispute,
+/// this moves the job to Resolved on the final milestone and on to the next
+/// milestone otherwise. Arbitrators can still resolve the dispute normally
+/// until this is called.
+///
+/// The split is paid through SplitPayout like resolve_dispute, except that
+/// no arbitration fee is charged: the protocol fee applies to the agent's
+/// share and, on the final milestone, the bond is split by the fallback
+/// `bond_agent_percentage`.
+/// Payouts are added to the agent's AgentProfile volume, but the outcome is
+/// not counted as a dispute won or lost since nobody judged it. Any votes
+/// already cast are discarded and their record closed to `vote_payer`, the
+/// arbitrator who cast the first vote; any Evidence records are closed back
+/// to their parties.
+///
+/// SECURITY:
+/// - Only Disputed jobs past their deadline
+/// - Job, escrow and payee accounts validated by SplitPayout
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExpireDispute<'info>>) -> Result<()> {
+    let job = &ctx.accounts.payout.job;
+
+    require!(
+        job.status == JobStatus::Disputed,
+        EscrowError::NotDisputed
+    );
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp > job.dispute_deadline,
+        EscrowError::DisputeWindowOpen
+    );
+
+    let fallback = ctx.accounts.config.dispute_fallback;
+    let agent_percentage = fallback.agent_percentage;
+    let split = job.split_disputed(agent_percentage, fallback.bond_agent_percentage, false)?;
+
+    ctx.accounts.payout.pay_split(&split, ctx.remaining_accounts)?;
+
+    // ── Discard any partial vote (a re-created job starts fresh) ────────
+    let vote_payer = ctx.accounts.vote_payer.as_ref().map(|p| p.to_account_info());
+    close_dispute_vote(&ctx.accounts.dispute_vote.to_account_info(), vote_payer.as_ref())?;
+
+    let job = &ctx.accounts.payout.job;
+    emit!(DisputeExpired {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        agent_amount: split.agent_payout,
+        requester_amount: split.requester_amount,
+        fee: split.fee,
+        agent_percentage,
+        bond_agent_amount: split.bond_agent_amount,
+        bond_requester_amount: split.bond_requester_amount,
+    });
+
+    msg!(
+        "Dispute expired for job {}: {}% ({}) to agent, {}% ({}) to requester, {} fee",
+        job.job_id,
+        agent_percentage,
+        split.agent_payout,
+        100 - agent_percentage,
+        split.requester_amount,
+        split.fee
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    pub payout: SplitPayout<'info>,
+
+    /// Program config (fallback split source)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Anyone may expire a lapsed dispute
+    pub caller: Signer<'info>,
+
+    /// CHECK: The job's DisputeVote PDA, validated by seeds. It only exists
+    /// if some arbitrator voted, so it is closed in the handler when present.
+    #[account(
+        mut,
+        seeds = [b"dispute_vote", payout.job.key().as_ref()],
+        bump,
+    )]
+    pub dispute_vote: UncheckedAccount<'info>,
+
+    /// CHECK: Arbitrator who paid the vote record's rent, validated against
+    /// the record. Required only when the record exists.
+    #[account(mut)]
+    pub vote_payer: Option<UncheckedAccount<'info>>,
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::utils::transfer_tokens;
+use crate::events::EscrowIncreased;
+
+/// Requester tops up the escrow of an existing job when its scope grows,
+/// without cancelling it and losing the agent assignment.
+///
+/// Allowed while the job is Open, InProgress or UnderReview. The top-up is
+/// added to `job.amount` and to the final milestone's amount. As in
+/// create_job, Token-2022 transfer fees are accounted for by recording the
+/// net amount the escrow actually received.
+///
+/// Token jobs pass `escrow_token`, `requester_token`, `mint` and
+/// `token_program`; native SOL jobs pass `sol_vault` instead.
+///
+/// SECURITY:
+/// - PDA seed validation on job account
+/// - has_one = requester: only the job poster can add funds
+/// - Escrow token / SOL vault validated by PDA seeds
+/// - Requester token owner + mint validated
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, IncreaseAmount<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, EscrowError::ZeroAmount);
+
+    let job = &mut ctx.accounts.job;
+    require!(
+        matches!(
+            job.status,
+            JobStatus::Open | JobStatus::InProgress | JobStatus::UnderReview
+        ),
+        EscrowError::InvalidStatus
+    );
+
+    // ── Transfer the top-up into escrow ─────────────────────────────────
+    let added = match job.asset {
+        AssetKind::SplToken => {
+            let token_program = ctx
+                .accounts
+                .token_program
+                .as_ref()
+                .ok_or(EscrowError::MissingAccount)?;
+            let mint = ctx.accounts.mint.as_ref().ok_or(EscrowError::MissingAccount)?;
+            let requester_token = ctx
+                .accounts
+                .requester_token
+                .as_ref()
+                .ok_or(EscrowError::MissingAccount)?;
+            let escrow_token = ctx
+                .accounts
+                .escrow_token
+                .as_mut()
+                .ok_or(EscrowError::MissingAccount)?;
+            let before = escrow_token.amount;
+
+            transfer_tokens(
+                token_program,
+                requester_token.to_account_info(),
+                mint,
+                escrow_token.to_account_info(),
+                ctx.accounts.requester.to_account_info(),
+                ctx.remaining_accounts,
+                amount,
+                &[],
+            )?;
+
+            // Net of Token-2022 transfer fees
+            escrow_token.reload()?;
+            escrow_token
+                .amount
+                .checked_sub(before)
+                .ok_or(EscrowError::Overflow)?
+        }
+        AssetKind::NativeSol => {
+            let sol_vault = ctx
+                .accounts
+                .sol_vault
+                .as_ref()
+                .ok_or(EscrowError::MissingAccount)?;
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.requester.to_account_info(),
+                    to: sol_vault.to_account_info(),
+                },
+            );
+            system_program::transfer(cpi_ctx, amount)?;
+            amount
+        }
+    };
+    require!(added > 0, EscrowError::ZeroAmount);
+    job.increase_amount(added)?;
+
+    emit!(EscrowIncreased {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        added,
+        amount: job.amount,
+    });
+
+    msg!(
+        "Job {} escrow increased by {} to {}",
+        job.job_id,
+        added,
+        job.amount
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct IncreaseAmount<'info> {
+    /// SECURITY: PDA seeds + has_one = requester
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+        has_one = requester @ EscrowError::Unauthorized,
+    )]
+    pub job: Account<'info, Job>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // ── Token jobs ──────────────────────────────────────────────────────
+
+    /// SECURITY: Escrow token validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"escrow_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.escrow_token_bump,
+        constraint = escrow_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub escrow_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Requester token owner + mint validated
+    #[account(
+        mut,
+        constraint = requester_token.owner == requester.key() @ EscrowError::InvalidTokenOwner,
+        constraint = requester_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub requester_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Job mint, required by transfer_checked
+    #[account(
+        address = job.mint @ EscrowError::InvalidMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // ── Native SOL jobs ─────────────────────────────────────────────────
+
+    /// SECURITY: SOL vault validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"sol_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.vault_bump,
+    )]
+    pub sol_vault: Option<Account<'info, SolVault>>,
+}
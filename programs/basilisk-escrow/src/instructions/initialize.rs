@@ -1,18 +1,28 @@
 use anchor_lang::prelude::*;
-use crate::state::ProgramConfig;
+use crate::state::{ArbitrationFee, DisputeFallback, ProgramConfig};
 
 /// Initialize the program configuration.
-/// Called once after deployment to set admin and arbitrator.
-pub fn handler(ctx: Context<Initialize>, arbitrator: Pubkey) -> Result<()> {
+/// Called once after deployment to set the admin and the arbitrator panel.
+/// A dispute is resolved once `threshold` of the `arbitrators` have voted.
+/// The protocol and arbitration fees start at zero and jobs may name any
+/// arbitrator. Disputes nobody resolves within DEFAULT_DISPUTE_DAYS can be
+/// expired to a 50/50 split. All of these can be changed later via
+/// update_config.
+pub fn handler(ctx: Context<Initialize>, arbitrators: Vec<Pubkey>, threshold: u8) -> Result<()> {
     let config = &mut ctx.accounts.config;
     config.admin = ctx.accounts.admin.key();
-    config.arbitrator = arbitrator;
+    config.set_arbitrators(arbitrators, threshold)?;
+    config.require_registered_arbitrator = false;
+    config.arbitration_fee = ArbitrationFee::default();
+    config.dispute_fallback = DisputeFallback::default();
+    config.fee_bps = 0;
     config.bump = ctx.bumps.config;
 
     msg!(
-        "Program initialized: admin={}, arbitrator={}",
+        "Program initialized: admin={}, arbitrators={:?}, threshold={}",
         config.admin,
-        config.arbitrator
+        config.arbitrators,
+        config.threshold
     );
     Ok(())
 }
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+use crate::state::{
+    ArbitrationFee, DisputeFallback, LegacyProgramConfig, ProgramConfig,
+};
+use crate::errors::EscrowError;
+
+/// Migrate a ProgramConfig written by the original single-arbitrator program
+/// to the current layout (admin-only, callable once after an upgrade).
+///
+/// The account is grown to the current size, with the admin paying the extra
+/// rent. The old arbitrator becomes a one-member panel with threshold 1; the
+/// remaining fields get the same defaults initialize uses (no protocol or
+/// arbitration fee, any arbitrator may be named, DEFAULT_DISPUTE_DAYS 50/50
+/// dispute fallback).
+///
+/// SECURITY:
+/// - Config validated by PDA seeds and program ownership
+/// - Only the legacy layout (73 bytes) is accepted, so a migrated config
+///   cannot be overwritten
+/// - Signer must be the admin recorded in the legacy config
+pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config = ctx.accounts.config.to_account_info();
+
+    let legacy = {
+        let data = config.try_borrow_data()?;
+        require!(
+            data.len() == 8 + LegacyProgramConfig::LEN,
+            EscrowError::ConfigAlreadyMigrated
+        );
+        require!(
+            data[..8] == ProgramConfig::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyProgramConfig::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(
+        legacy.admin,
+        ctx.accounts.admin.key(),
+        EscrowError::Unauthorized
+    );
+
+    // ── Grow the account, topping up its rent ───────────────────────────
+    let space = 8 + ProgramConfig::LEN;
+    let rent = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(config.lamports());
+    if rent > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: config.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+    config.realloc(space, true)?;
+
+    let arbitrators = if legacy.arbitrator == Pubkey::default() {
+        Vec::new()
+    } else {
+        vec![legacy.arbitrator]
+    };
+    let migrated = ProgramConfig {
+        admin: legacy.admin,
+        threshold: arbitrators.len() as u8,
+        arbitrators,
+        require_registered_arbitrator: false,
+        arbitration_fee: ArbitrationFee::default(),
+        dispute_fallback: DisputeFallback::default(),
+        fee_bps: 0,
+        bump: legacy.bump,
+    };
+    migrated.try_serialize(&mut &mut config.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Config migrated: admin={}, arbitrators={:?}, threshold={}",
+        migrated.admin,
+        migrated.arbitrators,
+        migrated.threshold
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Legacy config, deserialized by hand since it does not match
+    /// the current layout. Validated by seeds and owner.
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        owner = crate::ID,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
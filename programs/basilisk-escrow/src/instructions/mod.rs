@@ -1,19 +1,69 @@
+// Every instruction module exposes its own `handler`; callers always use the
+// fully qualified path, so the overlapping glob re-exports are intentional.
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize;
 pub mod update_config;
+pub mod migrate_config;
+pub mod register_arbitrator;
+pub mod deregister_arbitrator;
+pub mod withdraw_fees;
+pub mod withdraw_sol_fees;
 pub mod create_job;
+pub mod create_sol_job;
+pub mod increase_amount;
 pub mod accept_job;
+pub mod abandon_job;
+pub mod apply_to_job;
+pub mod select_agent;
+pub mod unselect_agent;
+pub mod withdraw_application;
 pub mod submit_deliverable;
+pub mod propose_deadline_extension;
+pub mod accept_deadline_extension;
+pub mod payouts;
 pub mod approve_and_pay;
+pub mod request_revision;
 pub mod reject_work;
+pub mod submit_evidence;
+pub mod claim_after_review_timeout;
 pub mod cancel_job;
+pub mod propose_settlement;
+pub mod accept_settlement;
 pub mod resolve_dispute;
+pub mod expire_dispute;
+pub mod rate_requester;
+pub mod close_job;
 
 pub use initialize::*;
 pub use update_config::*;
+pub use migrate_config::*;
+pub use register_arbitrator::*;
+pub use deregister_arbitrator::*;
+pub use withdraw_fees::*;
+pub use withdraw_sol_fees::*;
 pub use create_job::*;
+pub use create_sol_job::*;
+pub use increase_amount::*;
 pub use accept_job::*;
+pub use abandon_job::*;
+pub use apply_to_job::*;
+pub use select_agent::*;
+pub use unselect_agent::*;
+pub use withdraw_application::*;
 pub use submit_deliverable::*;
+pub use propose_deadline_extension::*;
+pub use accept_deadline_extension::*;
+pub use payouts::*;
 pub use approve_and_pay::*;
+pub use request_revision::*;
 pub use reject_work::*;
+pub use submit_evidence::*;
+pub use claim_after_review_timeout::*;
 pub use cancel_job::*;
+pub use propose_settlement::*;
+pub use accept_settlement::*;
 pub use resolve_dispute::*;
+pub use expire_dispute::*;
+pub use rate_requester::*;
+pub use close_job::*;
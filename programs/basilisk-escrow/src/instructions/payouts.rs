@@ -0,0 +1,509 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::utils::{close_program_account, Escrow};
+
+// ============================================================================
+// MILESTONE PAYOUT - Releasing the current milestone to the agent
+// ============================================================================
+
+/// Outcome of releasing one milestone.
+pub struct MilestonePayment {
+    /// Paid to the agent, net of the protocol fee
+    pub amount: u64,
+    /// Protocol fee routed to the treasury
+    pub fee: u64,
+    /// Bond returned to the agent (final milestone only)
+    pub bond_returned: u64,
+    /// Whether this was the final milestone
+    pub completed: bool,
+}
+
+/// Job, escrow and payee accounts for releasing the current milestone to
+/// the agent. Shared by approve_and_pay and claim_after_review_timeout,
+/// which add their own authorization on top.
+///
+/// Token jobs pass the token accounts; native SOL jobs pass `sol_vault`,
+/// `agent` and `sol_treasury` instead.
+///
+/// SECURITY:
+/// - PDA seed validation on job, escrow authority, escrow token, SOL vault
+///   and bond escrow (cannot substitute fake accounts)
+/// - Agent token owner (or agent wallet) validated against job.agent
+/// - Mint consistency validated across all token accounts
+#[derive(Accounts)]
+pub struct MilestonePayout<'info> {
+    /// SECURITY: PDA seeds ensure this is a legitimate job account
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// CHECK: PDA authority for escrow. Validated by seeds constraint.
+    #[account(
+        seeds = [b"escrow", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.escrow_authority_bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    /// SECURITY: Assigned agent's reputation profile, validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"agent_profile", job.agent.as_ref()],
+        bump = agent_profile.bump,
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    // ── Token jobs ──────────────────────────────────────────────────────
+
+    /// SECURITY: Escrow token validated by PDA seeds — cannot be substituted
+    #[account(
+        mut,
+        seeds = [b"escrow_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.escrow_token_bump,
+        constraint = escrow_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub escrow_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Validates owner is the assigned agent AND mint matches
+    #[account(
+        mut,
+        constraint = agent_token.owner == job.agent @ EscrowError::InvalidTokenOwner,
+        constraint = agent_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub agent_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Treasury validated by PDA seeds for the job's mint
+    #[account(
+        mut,
+        seeds = [b"treasury", job.mint.as_ref()],
+        bump,
+    )]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Job mint, required by transfer_checked
+    #[account(
+        address = job.mint @ EscrowError::InvalidMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // ── Native SOL jobs ─────────────────────────────────────────────────
+
+    /// SECURITY: SOL vault validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"sol_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.vault_bump,
+    )]
+    pub sol_vault: Option<Account<'info, SolVault>>,
+
+    /// CHECK: Receives the payout. Validated against job.agent.
+    #[account(
+        mut,
+        address = job.agent @ EscrowError::InvalidRecipient,
+    )]
+    pub agent: Option<UncheckedAccount<'info>>,
+
+    /// SECURITY: SOL treasury validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"sol_treasury"],
+        bump,
+    )]
+    pub sol_treasury: Option<Account<'info, SolTreasury>>,
+
+    // ── Agent bond (bonded jobs only) ───────────────────────────────────
+
+    /// SECURITY: Bond escrow validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+        constraint = bond_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub bond_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Bond vault validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+    )]
+    pub bond_vault: Option<Account<'info, SolVault>>,
+}
+
+impl<'info> MilestonePayout<'info> {
+    /// Release milestone `milestone_index` of a job under review: the
+    /// protocol fee goes to the treasury and the rest to the agent, with
+    /// any bond returned on the final milestone. The payout is added to the
+    /// agent's AgentProfile, and the final milestone counts the job as
+    /// completed with `rating` (None when nobody rated it).
+    pub fn pay(
+        &mut self,
+        milestone_index: u8,
+        rating: Option<u8>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<MilestonePayment> {
+        require!(
+            self.job.status == JobStatus::UnderReview,
+            EscrowError::InvalidStatus
+        );
+
+        let job = &self.job;
+        let milestone_amount = job.milestone(milestone_index)?.amount;
+        let fee = job.protocol_fee(milestone_amount)?;
+        let amount = milestone_amount
+            .checked_sub(fee)
+            .ok_or(EscrowError::Overflow)?;
+        let bond_returned = if job.is_last_milestone() {
+            job.bond_balance
+        } else {
+            0
+        };
+
+        let seeds = job.escrow_authority_seeds();
+        let signer_seeds = &[&seeds[..]];
+
+        let escrow = Escrow::new(
+            job.asset,
+            &self.token_program,
+            &self.escrow_token,
+            &self.mint,
+            self.escrow_authority.to_account_info(),
+            self.sol_vault.as_ref().map(|v| v.to_account_info()),
+            remaining_accounts,
+        )?;
+
+        // ── Protocol fee to treasury ────────────────────────────────────
+        if fee > 0 {
+            let treasury = escrow.destination(
+                self.treasury_token.as_ref(),
+                self.sol_treasury.as_ref().map(|t| t.to_account_info()),
+            )?;
+            escrow.pay(treasury, fee, signer_seeds)?;
+        }
+
+        // ── Transfer from escrow to agent ───────────────────────────────
+        let agent = escrow.destination(
+            self.agent_token.as_ref(),
+            self.agent.as_ref().map(|a| a.to_account_info()),
+        )?;
+        escrow.pay(agent.clone(), amount, signer_seeds)?;
+
+        // ── Return the agent's bond on completion ───────────────────────
+        if bond_returned > 0 {
+            let bond = Escrow::new(
+                job.asset,
+                &self.token_program,
+                &self.bond_token,
+                &self.mint,
+                self.escrow_authority.to_account_info(),
+                self.bond_vault.as_ref().map(|v| v.to_account_info()),
+                remaining_accounts,
+            )?;
+            bond.pay(agent, bond_returned, signer_seeds)?;
+        }
+
+        let completed = self.job.settle_milestone(bond_returned)?;
+
+        // ── Update the agent's reputation ───────────────────────────────
+        self.agent_profile.record_payout(self.job.mint, amount)?;
+        if completed {
+            self.agent_profile.record_completion(rating)?;
+        }
+
+        Ok(MilestonePayment {
+            amount,
+            fee,
+            bond_returned,
+            completed,
+        })
+    }
+}
+
+// ============================================================================
+// SPLIT PAYOUT - Dividing the escrow between agent and requester
+// ============================================================================
+
+/// Job, escrow and payee accounts for splitting a disputed milestone, or
+/// the escrow not yet released, between the agent and the requester.
+/// Shared by resolve_dispute, expire_dispute and accept_settlement, which
+/// decide the split and add their own authorization.
+///
+/// Token jobs pass the token accounts; native SOL jobs pass `sol_vault`,
+/// `agent`, `requester` and `sol_treasury` instead.
+///
+/// SECURITY:
+/// - PDA seed validation on job, escrow authority, escrow token, SOL vault
+///   and bond escrow (cannot substitute fake accounts)
+/// - Agent + requester token accounts (or wallets) validated against the job
+/// - Mint consistency validated across all token accounts
+#[derive(Accounts)]
+pub struct SplitPayout<'info> {
+    /// SECURITY: PDA seeds ensure legitimate job account
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// CHECK: PDA authority for escrow. Validated by seeds.
+    #[account(
+        seeds = [b"escrow", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.escrow_authority_bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    /// SECURITY: Assigned agent's reputation profile, validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"agent_profile", job.agent.as_ref()],
+        bump = agent_profile.bump,
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    // ── Token jobs ──────────────────────────────────────────────────────
+
+    /// SECURITY: Escrow token validated by PDA seeds + mint check
+    #[account(
+        mut,
+        seeds = [b"escrow_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.escrow_token_bump,
+        constraint = escrow_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub escrow_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Agent token owner + mint validated against job record
+    #[account(
+        mut,
+        constraint = agent_token.owner == job.agent @ EscrowError::InvalidTokenOwner,
+        constraint = agent_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub agent_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Requester token owner + mint validated against job record
+    #[account(
+        mut,
+        constraint = requester_token.owner == job.requester @ EscrowError::InvalidTokenOwner,
+        constraint = requester_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub requester_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Treasury validated by PDA seeds for the job's mint
+    #[account(
+        mut,
+        seeds = [b"treasury", job.mint.as_ref()],
+        bump,
+    )]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Job mint, required by transfer_checked
+    #[account(
+        address = job.mint @ EscrowError::InvalidMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // ── Native SOL jobs ─────────────────────────────────────────────────
+
+    /// SECURITY: SOL vault validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"sol_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.vault_bump,
+    )]
+    pub sol_vault: Option<Account<'info, SolVault>>,
+
+    /// CHECK: Receives the agent's share. Validated against job.agent.
+    #[account(
+        mut,
+        address = job.agent @ EscrowError::InvalidRecipient,
+    )]
+    pub agent: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Receives the requester's share. Validated against job.requester.
+    #[account(
+        mut,
+        address = job.requester @ EscrowError::InvalidRecipient,
+    )]
+    pub requester: Option<UncheckedAccount<'info>>,
+
+    /// SECURITY: SOL treasury validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"sol_treasury"],
+        bump,
+    )]
+    pub sol_treasury: Option<Account<'info, SolTreasury>>,
+
+    // ── Agent bond (bonded jobs only) ───────────────────────────────────
+
+    /// SECURITY: Bond escrow validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_token", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+        constraint = bond_token.mint == job.mint @ EscrowError::InvalidMint,
+    )]
+    pub bond_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SECURITY: Bond vault validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"bond_vault", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bond_bump,
+    )]
+    pub bond_vault: Option<Account<'info, SolVault>>,
+
+    // ── Dispute evidence ────────────────────────────────────────────────
+
+    /// CHECK: The requester's Evidence PDA, validated by seeds. It only
+    /// exists if the requester submitted evidence; it is then closed to the
+    /// requester, who must be passed as `requester`.
+    #[account(
+        mut,
+        seeds = [b"evidence", job.key().as_ref(), job.requester.as_ref()],
+        bump,
+    )]
+    pub requester_evidence: UncheckedAccount<'info>,
+
+    /// CHECK: The agent's Evidence PDA, validated by seeds. It only exists
+    /// if the agent submitted evidence; it is then closed to the agent, who
+    /// must be passed as `agent`.
+    #[account(
+        mut,
+        seeds = [b"evidence", job.key().as_ref(), job.agent.as_ref()],
+        bump,
+    )]
+    pub agent_evidence: UncheckedAccount<'info>,
+}
+
+impl<'info> SplitPayout<'info> {
+    /// The job's escrow, for payments made outside the split itself (the
+    /// arbitration fee).
+    pub fn escrow<'a>(
+        &'a self,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> Result<Escrow<'a, 'info>> {
+        Escrow::new(
+            self.job.asset,
+            &self.token_program,
+            &self.escrow_token,
+            &self.mint,
+            self.escrow_authority.to_account_info(),
+            self.sol_vault.as_ref().map(|v| v.to_account_info()),
+            remaining_accounts,
+        )
+    }
+
+    /// Pay out `split`: the protocol fee to the treasury, then each party's
+    /// share of the escrow and of the bond. The agent's payout is added to
+    /// its AgentProfile volume and any Evidence records are closed back to
+    /// their parties. Any arbitration fee must already have been paid.
+    ///
+    /// A split that ends the job leaves it Resolved with no bond or pending
+    /// proposals left. A split of a non-final milestone releases that
+    /// milestone instead and returns the job to InProgress for the next
+    /// one, with at least one review period to deliver it.
+    pub fn pay_split(
+        &mut self,
+        split: &EscrowSplit,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        {
+            let job = &self.job;
+            let seeds = job.escrow_authority_seeds();
+            let signer_seeds = &[&seeds[..]];
+            let escrow = self.escrow(remaining_accounts)?;
+
+            // ── Protocol fee to treasury ────────────────────────────────
+            if split.fee > 0 {
+                let treasury = escrow.destination(
+                    self.treasury_token.as_ref(),
+                    self.sol_treasury.as_ref().map(|t| t.to_account_info()),
+                )?;
+                escrow.pay(treasury, split.fee, signer_seeds)?;
+            }
+
+            let bond = if job.bond_balance > 0 {
+                Some(Escrow::new(
+                    job.asset,
+                    &self.token_program,
+                    &self.bond_token,
+                    &self.mint,
+                    self.escrow_authority.to_account_info(),
+                    self.bond_vault.as_ref().map(|v| v.to_account_info()),
+                    remaining_accounts,
+                )?)
+            } else {
+                None
+            };
+
+            // ── Pay agent their portion (and bond share) ────────────────
+            if split.agent_payout > 0 || split.bond_agent_amount > 0 {
+                let agent = escrow.destination(
+                    self.agent_token.as_ref(),
+                    self.agent.as_ref().map(|a| a.to_account_info()),
+                )?;
+                if split.agent_payout > 0 {
+                    escrow.pay(agent.clone(), split.agent_payout, signer_seeds)?;
+                }
+                if let (Some(bond), true) = (bond.as_ref(), split.bond_agent_amount > 0) {
+                    bond.pay(agent, split.bond_agent_amount, signer_seeds)?;
+                }
+            }
+
+            // ── Refund requester their portion (and bond share) ─────────
+            if split.requester_amount > 0 || split.bond_requester_amount > 0 {
+                let requester = escrow.destination(
+                    self.requester_token.as_ref(),
+                    self.requester.as_ref().map(|r| r.to_account_info()),
+                )?;
+                if split.requester_amount > 0 {
+                    escrow.pay(requester.clone(), split.requester_amount, signer_seeds)?;
+                }
+                if let (Some(bond), true) = (bond.as_ref(), split.bond_requester_amount > 0) {
+                    bond.pay(requester, split.bond_requester_amount, signer_seeds)?;
+                }
+            }
+        }
+
+        self.agent_profile
+            .record_payout(self.job.mint, split.agent_payout)?;
+
+        let job = &mut self.job;
+        let now = Clock::get()?.unix_timestamp;
+        job.disputed = false;
+        if split.ends_job {
+            job.bond_balance = 0;
+            job.status = JobStatus::Resolved;
+            job.settled_at = now;
+            job.clear_proposals();
+        } else {
+            job.settle_milestone(0)?;
+            job.extend_for_resubmission(now)?;
+        }
+
+        // ── Close the parties' evidence records back to them ────────────
+        let requester_evidence = self.requester_evidence.to_account_info();
+        if requester_evidence.owner == &crate::ID {
+            let requester = self.requester.as_ref().ok_or(EscrowError::MissingAccount)?;
+            close_program_account(&requester_evidence, &requester.to_account_info())?;
+        }
+        let agent_evidence = self.agent_evidence.to_account_info();
+        if agent_evidence.owner == &crate::ID {
+            let agent = self.agent.as_ref().ok_or(EscrowError::MissingAccount)?;
+            close_program_account(&agent_evidence, &agent.to_account_info())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::DeadlineExtensionProposed;
+
+/// Requester or agent proposes moving the job deadline to `new_deadline`.
+///
+/// The extension only takes effect once the other party calls
+/// accept_deadline_extension. A new proposal from either party replaces any
+/// pending one, and a change of the job's status or escrow drops it.
+/// Proposals are allowed after the deadline has passed, so an expired job
+/// can still be rescued as long as the requester has not cancelled it. A
+/// job can be extended at most MAX_DEADLINE_EXTENSIONS times.
+///
+/// SECURITY:
+/// - PDA seed validation on job account
+/// - Only the job's requester or assigned agent can propose
+pub fn handler(ctx: Context<ProposeDeadlineExtension>, new_deadline: i64) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+
+    require!(
+        matches!(job.status, JobStatus::InProgress | JobStatus::UnderReview),
+        EscrowError::InvalidStatus
+    );
+    require!(
+        job.extensions < MAX_DEADLINE_EXTENSIONS,
+        EscrowError::ExtensionLimitReached
+    );
+    require!(new_deadline > job.deadline, EscrowError::InvalidExtension);
+
+    job.extension_proposer = ctx.accounts.party.key();
+    job.proposed_deadline = new_deadline;
+
+    emit!(DeadlineExtensionProposed {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        proposer: job.extension_proposer,
+        new_deadline,
+    });
+
+    msg!(
+        "Deadline extension proposed for job {}: {} -> {}",
+        job.job_id,
+        job.deadline,
+        new_deadline
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeDeadlineExtension<'info> {
+    /// SECURITY: PDA seeds ensure legitimate job account
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// SECURITY: Must be the job's requester or assigned agent
+    #[account(
+        constraint = party.key() == job.requester || party.key() == job.agent
+            @ EscrowError::Unauthorized,
+    )]
+    pub party: Signer<'info>,
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::SettlementProposed;
+
+/// Requester or agent proposes settling the job early by splitting the
+/// escrow not yet released: `agent_percentage` of it (and
+/// `bond_agent_percentage` of any bond) to the agent, the rest to the
+/// requester.
+///
+/// The split only happens once the other party calls accept_settlement with
+/// the same percentages. A new proposal from either party replaces any
+/// pending one, and any change to the job's status or remaining escrow (a
+/// milestone payout, top-up, submission, revision or rejection) drops it,
+/// so it is never accepted against a balance it was not proposed on.
+/// Available while work is InProgress, UnderReview or Disputed, so a
+/// dispute can still be settled without waiting for the arbitrators.
+///
+/// SECURITY:
+/// - PDA seed validation on job account
+/// - Only the job's requester or assigned agent can propose
+pub fn handler(
+    ctx: Context<ProposeSettlement>,
+    agent_percentage: u8,
+    bond_agent_percentage: u8,
+) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+
+    require!(
+        matches!(
+            job.status,
+            JobStatus::InProgress | JobStatus::UnderReview | JobStatus::Disputed
+        ),
+        EscrowError::InvalidStatus
+    );
+    require!(agent_percentage <= 100, EscrowError::InvalidPercentage);
+    require!(bond_agent_percentage <= 100, EscrowError::InvalidPercentage);
+
+    job.settlement_proposer = ctx.accounts.party.key();
+    job.settlement_agent_percentage = agent_percentage;
+    job.settlement_bond_agent_percentage = bond_agent_percentage;
+
+    emit!(SettlementProposed {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        proposer: job.settlement_proposer,
+        agent_percentage,
+        bond_agent_percentage,
+    });
+
+    msg!(
+        "Settlement proposed for job {}: {}% to agent ({}% of bond)",
+        job.job_id,
+        agent_percentage,
+        bond_agent_percentage
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeSettlement<'info> {
+    /// SECURITY: PDA seeds ensure legitimate job account
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// SECURITY: Must be the job's requester or assigned agent
+    #[account(
+        constraint = party.key() == job.requester || party.key() == job.agent
+            @ EscrowError::Unauthorized,
+    )]
+    pub party: Signer<'info>,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::RequesterRated;
+
+/// Agent rates the requester after the job has settled.
+///
+/// Available once the job is Completed or Resolved, and only once per job.
+/// The requester cannot close the job until the agent has rated or
+/// RATING_WINDOW_DAYS have passed, so the agent always gets the chance.
+/// The score is stored on the Job and added to the requester's
+/// RequesterProfile, which is created here on their first rating (paid for
+/// by the agent), so agents can screen out requesters who reject good work.
+///
+/// SECURITY:
+/// - PDA seed validation on job and requester profile
+/// - Only the job's assigned agent can rate
+pub fn handler(ctx: Context<RateRequester>, rating: u8) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+
+    require!(
+        matches!(job.status, JobStatus::Completed | JobStatus::Resolved),
+        EscrowError::InvalidStatus
+    );
+    require!((1..=5).contains(&rating), EscrowError::InvalidRating);
+    require!(job.requester_rating == 0, EscrowError::AlreadyRated);
+
+    job.requester_rating = rating;
+
+    let profile = &mut ctx.accounts.requester_profile;
+    if profile.requester == Pubkey::default() {
+        profile.requester = job.requester;
+        profile.bump = ctx.bumps.requester_profile;
+    }
+    profile.record_rating(rating)?;
+
+    emit!(RequesterRated {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        requester: job.requester,
+        agent: job.agent,
+        rating,
+    });
+
+    msg!(
+        "Job {} - requester {} rated {} by agent",
+        job.job_id,
+        job.requester,
+        rating
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RateRequester<'info> {
+    /// SECURITY: PDA seeds + has_one = agent
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+        has_one = agent @ EscrowError::Unauthorized,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// Requester's aggregated ratings, created on their first rating
+    #[account(
+        init_if_needed,
+        payer = agent,
+        space = 8 + RequesterProfile::LEN,
+        seeds = [b"requester_profile", job.requester.as_ref()],
+        bump,
+    )]
+    pub requester_profile: Account<'info, RequesterProfile>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
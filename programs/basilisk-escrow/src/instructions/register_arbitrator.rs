@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{ArbitratorRegistration, ProgramConfig};
+use crate::errors::EscrowError;
+use crate::events::ArbitratorRegistered;
+
+/// Add an arbitrator to the registry (admin-only).
+///
+/// When `require_registered_arbitrator` is set in the config, create_job and
+/// create_sol_job only accept arbitrators that have a registration here.
+pub fn handler(ctx: Context<RegisterArbitrator>, arbitrator: Pubkey) -> Result<()> {
+    require!(arbitrator != Pubkey::default(), EscrowError::InvalidArbitrator);
+
+    let registration = &mut ctx.accounts.registration;
+    registration.arbitrator = arbitrator;
+    registration.registered_at = Clock::get()?.unix_timestamp;
+    registration.bump = ctx.bumps.registration;
+
+    emit!(ArbitratorRegistered {
+        arbitrator,
+        registered: true,
+    });
+
+    msg!("Arbitrator registered: {}", arbitrator);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(arbitrator: Pubkey)]
+pub struct RegisterArbitrator<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ArbitratorRegistration::LEN,
+        seeds = [b"arbitrator", arbitrator.as_ref()],
+        bump,
+    )]
+    pub registration: Account<'info, ArbitratorRegistration>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,30 +1,72 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::EscrowError;
+use crate::events::JobRejected;
 
-/// Requester rejects submitted work, opening a dispute.
+/// Requester rejects the current milestone's submitted work, opening a
+/// dispute over that milestone's escrow. Later milestones are not part of
+/// the dispute: they stay escrowed, and once a non-final milestone is
+/// decided the job moves on to the next one. Work can only be rejected
+/// while the review window is open.
+///
+/// Opening the dispute starts the config's dispute window; if no arbitrator
+/// resolves it in time, anyone can apply the fallback split via
+/// expire_dispute. The reason is carried by the JobRejected event; material
+/// for the arbitrators belongs in submit_evidence.
+///
+/// If the job still has revisions left, the requester is expected to use
+/// request_revision instead; rejecting then requires `force_dispute` to
+/// confirm that a dispute is really wanted.
 ///
 /// SECURITY FIX: Added PDA seed validation and has_one = requester
 /// to prevent unauthorized rejection.
-pub fn handler(ctx: Context<RejectWork>, reason: String) -> Result<()> {
+pub fn handler(
+    ctx: Context<RejectWork>,
+    milestone_index: u8,
+    reason: String,
+    force_dispute: bool,
+) -> Result<()> {
+    require!(reason.len() <= MAX_FEEDBACK_LEN, EscrowError::FeedbackTooLong);
+
     let job = &mut ctx.accounts.job;
 
     require!(
         job.status == JobStatus::UnderReview,
         EscrowError::InvalidStatus
     );
+    job.milestone(milestone_index)?;
+    require!(
+        force_dispute || job.revisions >= job.max_revisions,
+        EscrowError::RevisionsRemaining
+    );
 
-    let new_deliverable = format!("{} | REJECTED: {}", job.deliverable, reason);
+    let clock = Clock::get()?;
     require!(
-        new_deliverable.len() <= MAX_DELIVERABLE_LEN,
-        EscrowError::DeliverableTooLong
+        clock.unix_timestamp <= job.review_deadline()?,
+        EscrowError::ReviewWindowExpired
     );
 
     job.status = JobStatus::Disputed;
     job.disputed = true;
-    job.deliverable = new_deliverable;
+    job.clear_proposals();
+    job.dispute_deadline = clock
+        .unix_timestamp
+        .checked_add(ctx.accounts.config.dispute_fallback.period())
+        .ok_or(EscrowError::Overflow)?;
 
-    msg!("Job {} rejected - dispute opened", job.job_id);
+    emit!(JobRejected {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        milestone: milestone_index,
+        reason,
+        dispute_deadline: job.dispute_deadline,
+    });
+
+    msg!(
+        "Job {} rejected at milestone {} - dispute opened",
+        job.job_id,
+        milestone_index
+    );
     Ok(())
 }
 
@@ -33,11 +75,18 @@ pub struct RejectWork<'info> {
     /// SECURITY: PDA seeds + has_one = requester
     #[account(
         mut,
-        seeds = [b"job", job.job_id.as_bytes()],
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
         bump = job.bump,
         has_one = requester @ EscrowError::Unauthorized,
     )]
     pub job: Account<'info, Job>,
 
+    /// Program config (dispute window source)
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
     pub requester: Signer<'info>,
 }
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::RevisionRequested;
+
+/// Requester asks for changes to the current milestone's submitted work
+/// instead of disputing it.
+///
+/// The job goes back to InProgress and the agent resubmits via
+/// submit_deliverable (which starts a new review window). The feedback is
+/// carried by the RevisionRequested event rather than stored on the job.
+/// Each job allows up to `max_revisions` requests, set in its terms at
+/// creation; once they are used up the requester can only approve or
+/// reject. Like rejection, a revision can only be requested while the
+/// review window is open, and only before the job's deadline.
+///
+/// The agent is always left at least one review period to resubmit: if the
+/// current milestone's deadline (or the job's) would end sooner, it is
+/// pushed back, so a revision requested just before the deadline cannot be
+/// used to let the job expire and cancel it for a full refund.
+///
+/// SECURITY:
+/// - PDA seed validation on job account
+/// - has_one = requester
+/// - Deadline extended to leave a resubmission window
+pub fn handler(
+    ctx: Context<RequestRevision>,
+    milestone_index: u8,
+    feedback: String,
+) -> Result<()> {
+    require!(feedback.len() <= MAX_FEEDBACK_LEN, EscrowError::FeedbackTooLong);
+
+    let job = &mut ctx.accounts.job;
+
+    require!(
+        job.status == JobStatus::UnderReview,
+        EscrowError::InvalidStatus
+    );
+    job.milestone(milestone_index)?;
+    require!(
+        job.revisions < job.max_revisions,
+        EscrowError::RevisionLimitReached
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp <= job.review_deadline()?,
+        EscrowError::ReviewWindowExpired
+    );
+    require!(
+        clock.unix_timestamp <= job.current_deadline(),
+        EscrowError::DeadlineExpired
+    );
+
+    job.status = JobStatus::InProgress;
+    job.revisions = job.revisions.checked_add(1).ok_or(EscrowError::Overflow)?;
+    job.clear_proposals();
+    let resubmit_by = job.extend_for_resubmission(clock.unix_timestamp)?;
+
+    emit!(RevisionRequested {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        milestone: milestone_index,
+        feedback,
+        revisions: job.revisions,
+        resubmit_by,
+    });
+
+    msg!(
+        "Job {} revision {}/{} requested at milestone {}",
+        job.job_id,
+        job.revisions,
+        job.max_revisions,
+        milestone_index
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestRevision<'info> {
+    /// SECURITY: PDA seeds + has_one = requester
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+        has_one = requester @ EscrowError::Unauthorized,
+    )]
+    pub job: Account<'info, Job>,
+
+    pub requester: Signer<'info>,
+}
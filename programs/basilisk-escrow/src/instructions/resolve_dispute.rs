@@ -1,161 +1,266 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::TokenAccount;
 use crate::state::*;
 use crate::errors::EscrowError;
+use crate::events::{DisputeResolved, DisputeVoteCast};
+use super::payouts::*;
 
-/// Arbitrator resolves a disputed job by splitting escrowed funds.
+/// Panel arbitrator votes on how to split a disputed job's escrowed funds.
+///
+/// Each arbitrator in ProgramConfig casts one vote (`agent_percentage`,
+/// `bond_agent_percentage`) into the job's DisputeVote PDA. Once `threshold`
+/// current panel members have voted, the median of their votes is executed
+/// in the same call and the vote record is closed, refunding its rent to the
+/// arbitrator who cast the first vote (`vote_payer`, needed only when that
+/// is not the executing arbitrator). Every voter therefore passes the full
+/// set of payout accounts.
+///
+/// A job that named its own arbitrator at creation is decided by that
+/// arbitrator alone: their single vote executes immediately.
 ///
 /// ╔══════════════════════════════════════════════════════════════════════╗
-/// ║  CRITICAL SECURITY FIX #1: ARBITRATOR AUTHORIZATION                ║
-/// ║                                                                     ║
-/// ║  BEFORE: Any signer could call resolve_dispute and steal funds.    ║
-/// ║  The "arbitrator" account had NO authorization check — just a      ║
-/// ║  `/// CHECK:` comment saying "would check in production".          ║
-/// ║                                                                     ║
-/// ║  AFTER: Arbitrator is validated against ProgramConfig.arbitrator.   ║
-/// ║  The config PDA stores the authorized arbitrator pubkey, set by    ║
-/// ║  the admin during initialize. Only that exact pubkey can resolve   ║
-/// ║  disputes. Config can be updated via update_config (admin-only).   ║
+/// ║  CRITICAL SECURITY FIX #1: ARBITRATOR AUTHORIZATION                  ║
+/// ║                                                                      ║
+/// ║  BEFORE: Any signer could call resolve_dispute and steal funds.      ║
+/// ║  The "arbitrator" account had NO authorization check — just a        ║
+/// ║  `/// CHECK:` comment saying "would check in production".            ║
+/// ║                                                                      ║
+/// ║  AFTER: The signer must be the job's own arbitrator, if it named     ║
+/// ║  one, or a member of the ProgramConfig arbitrator panel. A panel     ║
+/// ║  dispute is only executed once `threshold` members have voted, at    ║
+/// ║  the median of their votes, so no single member can direct the       ║
+/// ║  funds. The panel is set by the admin in initialize and changed      ║
+/// ║  via update_config.                                                  ║
 /// ╚══════════════════════════════════════════════════════════════════════╝
 ///
 /// Additional security fixes:
-/// - Job PDA seed validation
 /// - Status check uses JobStatus::Disputed (not just bool flag)
-/// - Escrow token validated by PDA seeds
-/// - Agent + requester token accounts validated for owner AND mint
+/// - Job, escrow and payee accounts validated by SplitPayout
 /// - Overflow-safe arithmetic for percentage calculation
-pub fn handler(
-    ctx: Context<ResolveDispute>,
+///
+/// Only the disputed milestone's escrow is split. If it is not the final
+/// milestone, the job then returns to InProgress for the next one, with at
+/// least one review period to deliver it, and later milestones stay
+/// escrowed.
+///
+/// The job's arbitration fee is paid before the split, taken from each
+/// party's award according to the job's FeeBearer (loser-pays in proportion
+/// to the share lost, or half each). It is shared equally by the
+/// arbitrators whose votes counted towards the outcome, the executing
+/// arbitrator taking any rounding remainder. The executing arbitrator's
+/// share goes to `arbitrator_token`; the other counted voters' destinations
+/// are passed as the first remaining accounts, in the order the votes were
+/// cast. The protocol fee then applies only to the agent's remaining share.
+/// The split itself is paid through SplitPayout, shared with
+/// expire_dispute. Native SOL jobs pass `sol_vault`, `agent`, `requester`
+/// and `sol_treasury` in place of the token accounts; their arbitration fee
+/// goes to the arbitrators' wallets.
+///
+/// If the agent posted a bond and the final milestone is disputed,
+/// `bond_agent_percentage` of it is returned to the agent and the rest goes
+/// to the requester; on an earlier milestone the bond stays held. No fee is
+/// taken on the bond.
+///
+/// The outcome is recorded on the agent's AgentProfile: a dispute won if the
+/// agent receives at least half of the escrow, lost otherwise.
+///
+/// Arbitrators weigh the Evidence records both parties attached via
+/// submit_evidence. Every call passes both Evidence PDAs
+/// (`requester_evidence`, `agent_evidence`), and the executing call closes
+/// those that exist back to their parties along with the vote record.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ResolveDispute<'info>>,
     agent_percentage: u8,
+    bond_agent_percentage: u8,
 ) -> Result<()> {
-    let job = &mut ctx.accounts.job;
+    let job = &ctx.accounts.payout.job;
 
     require!(
         job.status == JobStatus::Disputed,
         EscrowError::NotDisputed
     );
     require!(agent_percentage <= 100, EscrowError::InvalidPercentage);
+    require!(bond_agent_percentage <= 100, EscrowError::InvalidPercentage);
 
-    // ── Overflow-safe split calculation ─────────────────────────────────
-    let agent_amount = (job.amount as u128)
-        .checked_mul(agent_percentage as u128)
-        .ok_or(EscrowError::Overflow)?
-        .checked_div(100)
-        .ok_or(EscrowError::Overflow)? as u64;
-    let requester_amount = job
-        .amount
-        .checked_sub(agent_amount)
-        .ok_or(EscrowError::Overflow)?;
-
-    let job_id_bytes = job.job_id.as_bytes();
-    let seeds: &[&[u8]] = &[
-        b"escrow",
-        job_id_bytes,
-        &[job.escrow_authority_bump],
-    ];
-    let signer_seeds = &[seeds];
-
-    // ── Pay agent their portion ─────────────────────────────────────────
-    if agent_amount > 0 {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_token.to_account_info(),
-            to: ctx.accounts.agent_token.to_account_info(),
-            authority: ctx.accounts.escrow_authority.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
-        token::transfer(cpi_ctx, agent_amount)?;
+    // ── Record this arbitrator's vote ───────────────────────────────────
+    let arbitrator = ctx.accounts.arbitrator.key();
+    let vote = &mut ctx.accounts.dispute_vote;
+    if vote.job == Pubkey::default() {
+        vote.job = job.key();
+        vote.payer = arbitrator;
+        vote.bump = ctx.bumps.dispute_vote;
     }
+    require!(
+        !vote.votes.iter().any(|v| v.arbitrator == arbitrator),
+        EscrowError::AlreadyVoted
+    );
+    // Drop votes of arbitrators removed from the panel to make room
+    let config = &ctx.accounts.config;
+    vote.votes.retain(|v| job.is_arbitrator(config, &v.arbitrator));
+    vote.votes.push(ArbitratorVote {
+        arbitrator,
+        agent_percentage,
+        bond_agent_percentage,
+    });
+
+    emit!(DisputeVoteCast {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        arbitrator,
+        agent_percentage,
+        bond_agent_percentage,
+        votes: vote.votes.len() as u8,
+        threshold: job.arbitration_threshold(config),
+    });
 
-    // ── Refund requester their portion ──────────────────────────────────
-    if requester_amount > 0 {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_token.to_account_info(),
-            to: ctx.accounts.requester_token.to_account_info(),
-            authority: ctx.accounts.escrow_authority.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
+    // ── Execute the median split once the threshold is reached ──────────
+    let Some((agent_percentage, bond_agent_percentage)) = vote.outcome(job, config) else {
+        msg!(
+            "Dispute vote for job {}: {}/{} votes",
+            job.job_id,
+            vote.votes.len(),
+            job.arbitration_threshold(config)
         );
-        token::transfer(cpi_ctx, requester_amount)?;
+        return Ok(());
+    };
+
+    let split = job.split_disputed(agent_percentage, bond_agent_percentage, true)?;
+
+    // ── Arbitration fee shared by the counted voters ────────────────────
+    // The executing arbitrator voted last; the others are paid through the
+    // leading remaining accounts.
+    let mut remaining_accounts = ctx.remaining_accounts;
+    if split.arbitration_fee > 0 {
+        let voters: Vec<Pubkey> = vote
+            .counted_votes(job, config)
+            .map(|v| v.arbitrator)
+            .collect();
+        let others = voters.len() - 1;
+        require!(
+            remaining_accounts.len() >= others,
+            EscrowError::MissingAccount
+        );
+        let (fee_payees, rest) = remaining_accounts.split_at(others);
+        remaining_accounts = rest;
+
+        let share = split.arbitration_fee / voters.len() as u64;
+        let seeds = job.escrow_authority_seeds();
+        let signer_seeds = &[&seeds[..]];
+        let escrow = ctx.accounts.payout.escrow(remaining_accounts)?;
+        if share > 0 {
+            for (voter, payee) in voters.iter().zip(fee_payees) {
+                escrow.pay(escrow.payee(payee, voter)?, share, signer_seeds)?;
+            }
+        }
+        let executor_share = split
+            .arbitration_fee
+            .checked_sub(share * others as u64)
+            .ok_or(EscrowError::Overflow)?;
+        let executor = escrow.destination(
+            ctx.accounts.arbitrator_token.as_ref(),
+            Some(ctx.accounts.arbitrator.to_account_info()),
+        )?;
+        escrow.pay(executor, executor_share, signer_seeds)?;
     }
 
-    job.status = JobStatus::Resolved;
-    job.disputed = false;
+    ctx.accounts.payout.pay_split(&split, remaining_accounts)?;
+
+    // ── Update the agent's reputation ───────────────────────────────────
+    ctx.accounts
+        .payout
+        .agent_profile
+        .record_dispute(agent_percentage)?;
+
+    // ── Close the vote record to its payer (a re-created job starts fresh)
+    let payer = if ctx.accounts.dispute_vote.payer == ctx.accounts.arbitrator.key() {
+        ctx.accounts.arbitrator.to_account_info()
+    } else {
+        let vote_payer = ctx
+            .accounts
+            .vote_payer
+            .as_ref()
+            .ok_or(EscrowError::MissingAccount)?;
+        require_keys_eq!(
+            vote_payer.key(),
+            ctx.accounts.dispute_vote.payer,
+            EscrowError::InvalidRecipient
+        );
+        vote_payer.to_account_info()
+    };
+    ctx.accounts.dispute_vote.close(payer)?;
+
+    let job = &ctx.accounts.payout.job;
+    emit!(DisputeResolved {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        agent_amount: split.agent_payout,
+        requester_amount: split.requester_amount,
+        fee: split.fee,
+        arbitration_fee: split.arbitration_fee,
+        agent_percentage,
+        bond_agent_amount: split.bond_agent_amount,
+        bond_requester_amount: split.bond_requester_amount,
+    });
 
     msg!(
-        "Dispute resolved for job {}: {}% ({}) to agent, {}% ({}) to requester",
+        "Dispute resolved for job {}: {}% ({}) to agent, {}% ({}) to requester, {} fee, {} arbitration fee",
         job.job_id,
         agent_percentage,
-        agent_amount,
+        split.agent_payout,
         100 - agent_percentage,
-        requester_amount
+        split.requester_amount,
+        split.fee,
+        split.arbitration_fee
     );
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct ResolveDispute<'info> {
-    /// SECURITY: PDA seeds ensure legitimate job account
-    #[account(
-        mut,
-        seeds = [b"job", job.job_id.as_bytes()],
-        bump = job.bump,
-    )]
-    pub job: Account<'info, Job>,
+    pub payout: SplitPayout<'info>,
 
-    /// SECURITY: ProgramConfig PDA stores the authorized arbitrator.
-    /// This is the core fix for the arbitrator authorization vulnerability.
+    /// SECURITY: ProgramConfig PDA holds the arbitrator panel and vote
+    /// threshold that decide disputes on jobs without their own arbitrator.
     #[account(
         seeds = [b"config"],
         bump = config.bump,
     )]
     pub config: Account<'info, ProgramConfig>,
 
-    /// CHECK: PDA authority for escrow. Validated by seeds.
+    /// SECURITY FIX: Arbitrator MUST be the job's own arbitrator, or on the
+    /// panel stored in ProgramConfig if the job names none. Without this
+    /// constraint, ANYONE could call resolve_dispute and direct funds to
+    /// arbitrary accounts.
     #[account(
-        seeds = [b"escrow", job.job_id.as_bytes()],
-        bump = job.escrow_authority_bump,
-    )]
-    pub escrow_authority: UncheckedAccount<'info>,
-
-    /// SECURITY FIX: Arbitrator MUST match the authorized arbitrator
-    /// stored in ProgramConfig. Without this constraint, ANYONE could
-    /// call resolve_dispute and direct funds to arbitrary accounts.
-    #[account(
-        constraint = arbitrator.key() == config.arbitrator @ EscrowError::UnauthorizedArbitrator,
+        mut,
+        constraint = payout.job.is_arbitrator(&config, &arbitrator.key()) @ EscrowError::UnauthorizedArbitrator,
     )]
     pub arbitrator: Signer<'info>,
 
-    /// SECURITY: Escrow token validated by PDA seeds + mint check
+    /// Votes cast on this dispute, created by the first voter
     #[account(
-        mut,
-        seeds = [b"escrow_token", job.job_id.as_bytes()],
-        bump = job.escrow_token_bump,
-        constraint = escrow_token.mint == job.mint @ EscrowError::InvalidMint,
+        init_if_needed,
+        payer = arbitrator,
+        space = 8 + DisputeVote::LEN,
+        seeds = [b"dispute_vote", payout.job.key().as_ref()],
+        bump,
     )]
-    pub escrow_token: Account<'info, TokenAccount>,
+    pub dispute_vote: Account<'info, DisputeVote>,
 
-    /// SECURITY: Agent token owner + mint validated against job record
+    /// SECURITY: Arbitrator token owner + mint validated (receives the
+    /// arbitration fee)
     #[account(
         mut,
-        constraint = agent_token.owner == job.agent @ EscrowError::InvalidTokenOwner,
-        constraint = agent_token.mint == job.mint @ EscrowError::InvalidMint,
+        constraint = arbitrator_token.owner == arbitrator.key() @ EscrowError::InvalidTokenOwner,
+        constraint = arbitrator_token.mint == payout.job.mint @ EscrowError::InvalidMint,
     )]
-    pub agent_token: Account<'info, TokenAccount>,
+    pub arbitrator_token: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// SECURITY: Requester token owner + mint validated against job record
-    #[account(
-        mut,
-        constraint = requester_token.owner == job.requester @ EscrowError::InvalidTokenOwner,
-        constraint = requester_token.mint == job.mint @ EscrowError::InvalidMint,
-    )]
-    pub requester_token: Account<'info, TokenAccount>,
+    /// CHECK: Arbitrator who cast the first vote and paid the vote record's
+    /// rent. Validated against the record; only needed by the executing
+    /// call when that is another arbitrator.
+    #[account(mut)]
+    pub vote_payer: Option<UncheckedAccount<'info>>,
 
-    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
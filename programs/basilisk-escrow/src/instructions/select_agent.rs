@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::JobAccepted;
+
+/// Requester selects one applicant, assigning them to the job.
+///
+/// Moves the job from Open to InProgress exactly like accept_job, then closes
+/// the winning application and any losing applications passed in
+/// `remaining_accounts`, returning each application's rent to its agent.
+/// Losing applications are passed as `[application, agent wallet]` pairs.
+///
+/// On a bonded job the requester cannot post the agent's bond, so the job
+/// stays Open with the agent reserved; the selected agent then calls
+/// accept_job to deposit the bond and start work. If they never do, the
+/// requester can release them with unselect_agent. As in accept_job, an
+/// agent can only be selected before the job's current deadline.
+///
+/// The selected agent's AgentProfile is created here, at the requester's
+/// expense, if they have never taken a job before.
+///
+/// SECURITY:
+/// - PDA seed validation on job and selected application
+/// - has_one = requester; only the job poster can select
+/// - Every closed application must belong to this job, and rent can only
+///   be returned to the agent who paid it
+/// - Applications are matched on the job's created_at as well as its
+///   address, so ones left over from a closed job with the same job_id
+///   are rejected
+/// - The selected agent is re-checked against the invite list and must
+///   not be the requester
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SelectAgent<'info>>) -> Result<()> {
+    let job_key = ctx.accounts.job.key();
+    let job = &mut ctx.accounts.job;
+    let selected = &ctx.accounts.application;
+
+    require!(job.status == JobStatus::Open, EscrowError::JobNotOpen);
+    require!(
+        job.agent == Pubkey::default(),
+        EscrowError::JobAlreadyTaken
+    );
+    require!(
+        Clock::get()?.unix_timestamp <= job.current_deadline(),
+        EscrowError::DeadlineExpired
+    );
+    require!(
+        selected.job_created_at == job.created_at,
+        EscrowError::InvalidApplication
+    );
+    require!(
+        job.is_agent_allowed(&selected.agent),
+        EscrowError::AgentNotInvited
+    );
+    require!(selected.agent != job.requester, EscrowError::InvalidAgent);
+
+    job.agent = selected.agent;
+    let started = job.required_bond()? == 0;
+    if started {
+        job.status = JobStatus::InProgress;
+    }
+
+    // ── Create the agent's reputation profile if needed ─────────────────
+    let profile = &mut ctx.accounts.agent_profile;
+    if profile.agent == Pubkey::default() {
+        profile.agent = job.agent;
+        profile.bump = ctx.bumps.agent_profile;
+    }
+
+    // ── Close losing applications ───────────────────────────────────────
+    let pairs = ctx.remaining_accounts.chunks_exact(2);
+    require!(pairs.remainder().is_empty(), EscrowError::InvalidApplication);
+    for pair in pairs {
+        let application = Account::<Application>::try_from(&pair[0])?;
+        require!(
+            application.job == job_key && application.job_created_at == job.created_at,
+            EscrowError::InvalidApplication
+        );
+        require!(
+            application.key() != selected.key(),
+            EscrowError::InvalidApplication
+        );
+        require!(
+            pair[1].key() == application.agent,
+            EscrowError::InvalidRecipient
+        );
+        application.close(pair[1].clone())?;
+    }
+
+    if started {
+        emit!(JobAccepted {
+            job: job_key,
+            job_id: job.job_id.clone(),
+            agent: job.agent,
+            bond: 0,
+        });
+    }
+
+    msg!(
+        "Agent {} selected for job {} (awaiting bond: {})",
+        job.agent,
+        job.job_id,
+        !started
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SelectAgent<'info> {
+    /// SECURITY: PDA seeds + has_one = requester
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+        has_one = requester @ EscrowError::Unauthorized,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// SECURITY: Selected application validated by PDA seeds for this job.
+    /// Rent goes back to the selected agent.
+    #[account(
+        mut,
+        seeds = [b"application", job.key().as_ref(), application.agent.as_ref()],
+        bump = application.bump,
+        constraint = application.job == job.key() @ EscrowError::InvalidApplication,
+        close = agent,
+    )]
+    pub application: Account<'info, Application>,
+
+    /// CHECK: Receives the application rent. Validated against application.agent.
+    #[account(
+        mut,
+        address = application.agent @ EscrowError::InvalidRecipient,
+    )]
+    pub agent: UncheckedAccount<'info>,
+
+    /// Selected agent's reputation profile, created here if this is their
+    /// first job
+    #[account(
+        init_if_needed,
+        payer = requester,
+        space = 8 + AgentProfile::LEN,
+        seeds = [b"agent_profile", agent.key().as_ref()],
+        bump,
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,8 +1,22 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::EscrowError;
+use crate::events::DeliverableSubmitted;
 
-/// Agent submits a deliverable for review.
+/// Agent submits a deliverable for the current milestone for review.
+///
+/// `milestone_index` must match the job's current milestone; milestones are
+/// delivered in order. The milestone's own deadline applies if it has one,
+/// and the job deadline always applies. Submitting starts the job's review
+/// window; if the requester neither approves nor rejects before it closes,
+/// the agent can be paid through claim_after_review_timeout.
+///
+/// The deliverable is recorded as the SHA-256 of its content plus a URI and
+/// content type, so the requester and arbitrators can check exactly what was
+/// delivered. Work made of several files lists the rest in `artifacts`
+/// (max MAX_ARTIFACTS); they are stored in the job's DeliverableArtifacts
+/// account, created on first use at the agent's expense, which must then be
+/// passed. Each submission replaces the previous one's artifacts.
 ///
 /// SECURITY FIX: Added PDA seed validation AND has_one = agent constraint
 /// to ensure only the assigned agent can submit deliverables. The original
@@ -11,14 +25,18 @@ use crate::errors::EscrowError;
 /// passed in.
 pub fn handler(
     ctx: Context<SubmitDeliverable>,
-    deliverable_url: String,
-    notes: String,
+    milestone_index: u8,
+    deliverable: Deliverable,
+    artifacts: Vec<Deliverable>,
 ) -> Result<()> {
-    let combined = format!("{} | {}", deliverable_url, notes);
+    deliverable.validate()?;
     require!(
-        combined.len() <= MAX_DELIVERABLE_LEN,
-        EscrowError::DeliverableTooLong
+        artifacts.len() <= MAX_ARTIFACTS,
+        EscrowError::TooManyArtifacts
     );
+    for artifact in &artifacts {
+        artifact.validate()?;
+    }
 
     let job = &mut ctx.accounts.job;
     require!(
@@ -26,16 +44,53 @@ pub fn handler(
         EscrowError::InvalidStatus
     );
 
+    job.milestone(milestone_index)?;
+
     let clock = Clock::get()?;
     require!(
-        clock.unix_timestamp <= job.deadline,
+        clock.unix_timestamp <= job.deadline && clock.unix_timestamp <= job.current_deadline(),
         EscrowError::DeadlineExpired
     );
 
-    job.deliverable = combined;
+    // ── Record extra artifacts ──────────────────────────────────────────
+    match (ctx.accounts.artifacts.as_mut(), ctx.bumps.artifacts) {
+        (Some(record), Some(bump)) => {
+            record.job = job.key();
+            record.agent = job.agent;
+            record.milestone = milestone_index;
+            record.submitted_at = clock.unix_timestamp;
+            record.artifacts = artifacts;
+            record.bump = bump;
+        }
+        _ => require!(artifacts.is_empty(), EscrowError::MissingAccount),
+    }
+
+    job.artifact_count = ctx
+        .accounts
+        .artifacts
+        .as_ref()
+        .map_or(0, |record| record.artifacts.len() as u8);
+    job.deliverable = deliverable;
     job.status = JobStatus::UnderReview;
+    job.submitted_at = clock.unix_timestamp;
+    job.clear_proposals();
+
+    emit!(DeliverableSubmitted {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        agent: job.agent,
+        milestone: milestone_index,
+        content_hash: job.deliverable.content_hash,
+        uri: job.deliverable.uri.clone(),
+        content_type: job.deliverable.content_type.clone(),
+        artifact_count: job.artifact_count,
+    });
 
-    msg!("Deliverable submitted for job {}", job.job_id);
+    msg!(
+        "Deliverable submitted for job {} (milestone {})",
+        job.job_id,
+        milestone_index
+    );
     Ok(())
 }
 
@@ -46,11 +101,24 @@ pub struct SubmitDeliverable<'info> {
     /// 2. The signer is the assigned agent for THIS specific job
     #[account(
         mut,
-        seeds = [b"job", job.job_id.as_bytes()],
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
         bump = job.bump,
         has_one = agent @ EscrowError::Unauthorized,
     )]
     pub job: Account<'info, Job>,
 
+    /// Extra artifacts of the submission, created on first use
+    #[account(
+        init_if_needed,
+        payer = agent,
+        space = 8 + DeliverableArtifacts::LEN,
+        seeds = [b"artifacts", job.key().as_ref()],
+        bump,
+    )]
+    pub artifacts: Option<Account<'info, DeliverableArtifacts>>,
+
+    #[account(mut)]
     pub agent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::EvidenceSubmitted;
+
+/// Requester or agent attaches evidence to a disputed job.
+///
+/// Each party has its own Evidence PDA, created on their first submission
+/// (paid for by that party), holding up to MAX_EVIDENCE_ITEMS content hashes
+/// with the URI the content can be fetched from. Evidence is accepted until
+/// the job's dispute deadline. Arbitrators read both records before voting,
+/// and they are closed back to their parties once the dispute is settled by
+/// resolve_dispute or expire_dispute.
+///
+/// SECURITY:
+/// - PDA seed validation on job and evidence record
+/// - Only the job's requester or assigned agent can submit
+pub fn handler(
+    ctx: Context<SubmitEvidence>,
+    content_hash: [u8; 32],
+    uri: String,
+) -> Result<()> {
+    require!(
+        uri.len() <= MAX_EVIDENCE_URI_LEN,
+        EscrowError::EvidenceUriTooLong
+    );
+
+    let job = &ctx.accounts.job;
+    require!(
+        job.status == JobStatus::Disputed,
+        EscrowError::NotDisputed
+    );
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp <= job.dispute_deadline,
+        EscrowError::EvidenceWindowClosed
+    );
+
+    let party = ctx.accounts.party.key();
+    let evidence = &mut ctx.accounts.evidence;
+    if evidence.dispute_deadline != job.dispute_deadline {
+        evidence.job = job.key();
+        evidence.party = party;
+        evidence.dispute_deadline = job.dispute_deadline;
+        evidence.items.clear();
+        evidence.bump = ctx.bumps.evidence;
+    }
+    require!(
+        evidence.items.len() < MAX_EVIDENCE_ITEMS,
+        EscrowError::TooManyEvidenceItems
+    );
+    evidence.items.push(EvidenceItem {
+        content_hash,
+        uri: uri.clone(),
+        submitted_at: clock.unix_timestamp,
+    });
+
+    emit!(EvidenceSubmitted {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        party,
+        content_hash,
+        uri,
+        count: evidence.items.len() as u8,
+    });
+
+    msg!(
+        "Evidence {} of {} submitted for job {} by {}",
+        evidence.items.len(),
+        MAX_EVIDENCE_ITEMS,
+        job.job_id,
+        party
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitEvidence<'info> {
+    /// SECURITY: PDA seeds ensure legitimate job account
+    #[account(
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+    )]
+    pub job: Account<'info, Job>,
+
+    /// Submitting party's evidence record, created on their first submission
+    #[account(
+        init_if_needed,
+        payer = party,
+        space = 8 + Evidence::LEN,
+        seeds = [b"evidence", job.key().as_ref(), party.key().as_ref()],
+        bump,
+    )]
+    pub evidence: Account<'info, Evidence>,
+
+    /// SECURITY: Must be the job's requester or assigned agent
+    #[account(
+        mut,
+        constraint = party.key() == job.requester || party.key() == job.agent
+            @ EscrowError::Unauthorized,
+    )]
+    pub party: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::events::AgentUnselected;
+
+/// Requester releases the agent reserved by select_agent on a bonded job
+/// who has not posted the bond.
+///
+/// select_agent leaves a bonded job Open with the agent reserved until they
+/// call accept_job. If they never do, this clears the reservation: the job
+/// stays Open with no agent and keeps its escrow and deadline, so the
+/// requester can take new applications and select someone else, or any
+/// invited agent can accept it directly. Once the bond is posted the job is
+/// InProgress and can no longer be unselected.
+///
+/// SECURITY:
+/// - PDA seed validation on job account
+/// - has_one = requester; only the job poster can release the reservation
+pub fn handler(ctx: Context<UnselectAgent>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+
+    require!(job.status == JobStatus::Open, EscrowError::JobNotOpen);
+    require!(job.agent != Pubkey::default(), EscrowError::InvalidAgent);
+
+    let agent = job.agent;
+    job.agent = Pubkey::default();
+
+    emit!(AgentUnselected {
+        job: job.key(),
+        job_id: job.job_id.clone(),
+        agent,
+    });
+
+    msg!("Agent {} unselected from job {}", agent, job.job_id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnselectAgent<'info> {
+    /// SECURITY: PDA seeds + has_one = requester
+    #[account(
+        mut,
+        seeds = [b"job", job.requester.as_ref(), job.job_id_hash.as_ref()],
+        bump = job.bump,
+        has_one = requester @ EscrowError::Unauthorized,
+    )]
+    pub job: Account<'info, Job>,
+
+    pub requester: Signer<'info>,
+}
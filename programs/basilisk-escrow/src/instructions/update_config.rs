@@ -1,19 +1,47 @@
 use anchor_lang::prelude::*;
-use crate::state::ProgramConfig;
+use crate::state::{ArbitrationFee, DisputeFallback, ProgramConfig, MAX_FEE_BPS};
 use crate::errors::EscrowError;
+use crate::events::ConfigUpdated;
 
 /// Update program configuration (admin-only).
-/// Allows changing the arbitrator or transferring admin rights.
+/// Allows replacing the arbitrator panel or its voting threshold, transferring
+/// admin rights or changing the protocol fee. Fee changes only apply to jobs
+/// created afterwards. Votes already cast by arbitrators who are removed from
+/// the panel no longer count towards an open dispute.
+///
+/// `new_require_registered_arbitrator` restricts the arbitrators that new
+/// jobs may name to those in the registry. Jobs already created keep their
+/// arbitrator either way.
+///
+/// `new_arbitration_fee` sets the default arbitration fee that new jobs
+/// snapshot unless their terms carry their own.
+///
+/// `new_dispute_fallback` sets how long arbitrators have to resolve a
+/// dispute and the split expire_dispute applies once that window lapses.
+/// The window applies to disputes opened afterwards; the split applies to
+/// every dispute expired afterwards.
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<UpdateConfig>,
-    new_arbitrator: Option<Pubkey>,
+    new_arbitrators: Option<Vec<Pubkey>>,
     new_admin: Option<Pubkey>,
+    new_fee_bps: Option<u16>,
+    new_threshold: Option<u8>,
+    new_require_registered_arbitrator: Option<bool>,
+    new_arbitration_fee: Option<ArbitrationFee>,
+    new_dispute_fallback: Option<DisputeFallback>,
 ) -> Result<()> {
     let config = &mut ctx.accounts.config;
 
-    if let Some(arbitrator) = new_arbitrator {
-        msg!("Arbitrator updated: {} -> {}", config.arbitrator, arbitrator);
-        config.arbitrator = arbitrator;
+    if new_arbitrators.is_some() || new_threshold.is_some() {
+        let arbitrators = new_arbitrators.unwrap_or_else(|| config.arbitrators.clone());
+        let threshold = new_threshold.unwrap_or(config.threshold);
+        config.set_arbitrators(arbitrators, threshold)?;
+        msg!(
+            "Arbitrators updated: {:?} (threshold {})",
+            config.arbitrators,
+            config.threshold
+        );
     }
 
     if let Some(admin) = new_admin {
@@ -21,6 +49,51 @@ pub fn handler(
         config.admin = admin;
     }
 
+    if let Some(fee_bps) = new_fee_bps {
+        require!(fee_bps <= MAX_FEE_BPS, EscrowError::FeeTooHigh);
+        msg!("Fee updated: {} -> {} bps", config.fee_bps, fee_bps);
+        config.fee_bps = fee_bps;
+    }
+
+    if let Some(required) = new_require_registered_arbitrator {
+        msg!(
+            "Registered arbitrator required: {} -> {}",
+            config.require_registered_arbitrator,
+            required
+        );
+        config.require_registered_arbitrator = required;
+    }
+
+    if let Some(arbitration_fee) = new_arbitration_fee {
+        arbitration_fee.validate()?;
+        msg!(
+            "Arbitration fee updated: {:?} -> {:?}",
+            config.arbitration_fee,
+            arbitration_fee
+        );
+        config.arbitration_fee = arbitration_fee;
+    }
+
+    if let Some(dispute_fallback) = new_dispute_fallback {
+        dispute_fallback.validate()?;
+        msg!(
+            "Dispute fallback updated: {:?} -> {:?}",
+            config.dispute_fallback,
+            dispute_fallback
+        );
+        config.dispute_fallback = dispute_fallback;
+    }
+
+    emit!(ConfigUpdated {
+        admin: config.admin,
+        arbitrators: config.arbitrators.clone(),
+        threshold: config.threshold,
+        require_registered_arbitrator: config.require_registered_arbitrator,
+        arbitration_fee: config.arbitration_fee,
+        dispute_fallback: config.dispute_fallback,
+        fee_bps: config.fee_bps,
+    });
+
     Ok(())
 }
 
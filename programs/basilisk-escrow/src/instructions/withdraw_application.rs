@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+
+/// Agent withdraws their application, reclaiming its rent.
+///
+/// Allowed at any time, so applications left behind after another agent was
+/// selected (or the job was cancelled) never lock the applicant's rent.
+pub fn handler(ctx: Context<WithdrawApplication>) -> Result<()> {
+    msg!(
+        "Agent {} withdrew application for job {}",
+        ctx.accounts.agent.key(),
+        ctx.accounts.application.job
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawApplication<'info> {
+    /// SECURITY: PDA seeds + has_one = agent. Rent goes back to the agent.
+    #[account(
+        mut,
+        seeds = [b"application", application.job.as_ref(), agent.key().as_ref()],
+        bump = application.bump,
+        has_one = agent @ EscrowError::Unauthorized,
+        close = agent,
+    )]
+    pub application: Account<'info, Application>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+}
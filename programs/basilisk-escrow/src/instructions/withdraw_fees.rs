@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::ProgramConfig;
+use crate::errors::EscrowError;
+use crate::utils::transfer_tokens;
+use crate::events::FeesWithdrawn;
+
+/// Withdraw accumulated protocol fees from a mint's treasury (admin-only).
+///
+/// The treasury token account is a PDA owned by the config PDA, so only this
+/// instruction can move funds out of it.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFees<'info>>, amount: u64) -> Result<()> {
+    require!(amount > 0, EscrowError::ZeroAmount);
+    require!(
+        amount <= ctx.accounts.treasury_token.amount,
+        EscrowError::InsufficientTreasuryBalance
+    );
+
+    let config = &ctx.accounts.config;
+    let seeds: &[&[u8]] = &[b"config", &[config.bump]];
+    let signer_seeds = &[seeds];
+
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        ctx.accounts.treasury_token.to_account_info(),
+        &ctx.accounts.mint,
+        ctx.accounts.destination_token.to_account_info(),
+        config.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        signer_seeds,
+    )?;
+
+    emit!(FeesWithdrawn {
+        mint: ctx.accounts.treasury_token.mint,
+        destination: ctx.accounts.destination_token.key(),
+        amount,
+    });
+
+    msg!(
+        "Withdrew {} fee tokens (mint: {}) to {}",
+        amount,
+        ctx.accounts.treasury_token.mint,
+        ctx.accounts.destination_token.key()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub admin: Signer<'info>,
+
+    /// SECURITY: Treasury validated by PDA seeds for its own mint
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury_token.mint.as_ref()],
+        bump,
+    )]
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// SECURITY: Destination mint must match the treasury mint
+    #[account(
+        mut,
+        constraint = destination_token.mint == treasury_token.mint @ EscrowError::InvalidMint,
+    )]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// SECURITY: Treasury mint, required by transfer_checked
+    #[account(
+        address = treasury_token.mint @ EscrowError::InvalidMint,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
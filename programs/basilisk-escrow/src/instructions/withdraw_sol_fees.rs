@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::EscrowError;
+use crate::utils::transfer_lamports;
+use crate::events::FeesWithdrawn;
+
+/// Withdraw accumulated native SOL protocol fees (admin-only).
+///
+/// The SOL treasury is a program-owned PDA, so lamports are moved out of it
+/// directly. Its rent-exempt minimum is never withdrawn, keeping the account
+/// alive for future fees.
+pub fn handler(ctx: Context<WithdrawSolFees>, amount: u64) -> Result<()> {
+    require!(amount > 0, EscrowError::ZeroAmount);
+
+    let treasury = ctx.accounts.sol_treasury.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(treasury.data_len());
+    let available = treasury.lamports().saturating_sub(rent_exempt);
+    require!(amount <= available, EscrowError::InsufficientTreasuryBalance);
+
+    transfer_lamports(&treasury, &ctx.accounts.destination.to_account_info(), amount)?;
+
+    emit!(FeesWithdrawn {
+        mint: Pubkey::default(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+    });
+
+    msg!(
+        "Withdrew {} lamports of SOL fees to {}",
+        amount,
+        ctx.accounts.destination.key()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSolFees<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub admin: Signer<'info>,
+
+    /// SECURITY: SOL treasury validated by PDA seeds
+    #[account(
+        mut,
+        seeds = [b"sol_treasury"],
+        bump,
+    )]
+    pub sol_treasury: Account<'info, SolTreasury>,
+
+    /// CHECK: Any wallet chosen by the admin; only receives lamports.
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
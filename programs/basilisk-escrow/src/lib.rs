@@ -2,9 +2,12 @@ use anchor_lang::prelude::*;
 
 pub mod state;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod utils;
 
 use instructions::*;
+use state::{ArbitrationFee, Deliverable, DisputeFallback, JobTerms};
 
 // Program ID — will be replaced with actual deployed keypair address
 declare_id!("GXwWMznpFNaABnXj47ypdq3bvb1dfNBXijZ1m936ZFH1");
@@ -13,66 +16,254 @@ declare_id!("GXwWMznpFNaABnXj47ypdq3bvb1dfNBXijZ1m936ZFH1");
 pub mod basilisk_escrow {
     use super::*;
 
-    /// Initialize program configuration with admin and arbitrator.
+    /// Initialize program configuration with admin and arbitrator panel.
     /// Must be called once after deployment before any jobs can use disputes.
-    pub fn initialize(ctx: Context<Initialize>, arbitrator: Pubkey) -> Result<()> {
-        instructions::initialize::handler(ctx, arbitrator)
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        arbitrators: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::initialize::handler(ctx, arbitrators, threshold)
     }
 
     /// Update program configuration (admin-only).
+    #[allow(clippy::too_many_arguments)]
     pub fn update_config(
         ctx: Context<UpdateConfig>,
-        new_arbitrator: Option<Pubkey>,
+        new_arbitrators: Option<Vec<Pubkey>>,
         new_admin: Option<Pubkey>,
+        new_fee_bps: Option<u16>,
+        new_threshold: Option<u8>,
+        new_require_registered_arbitrator: Option<bool>,
+        new_arbitration_fee: Option<ArbitrationFee>,
+        new_dispute_fallback: Option<DisputeFallback>,
     ) -> Result<()> {
-        instructions::update_config::handler(ctx, new_arbitrator, new_admin)
+        instructions::update_config::handler(
+            ctx,
+            new_arbitrators,
+            new_admin,
+            new_fee_bps,
+            new_threshold,
+            new_require_registered_arbitrator,
+            new_arbitration_fee,
+            new_dispute_fallback,
+        )
+    }
+
+    /// Bring a config written by the original program up to the current
+    /// layout (admin-only, once after upgrading).
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config::handler(ctx)
+    }
+
+    /// Add an arbitrator to the registry jobs can pick from (admin-only).
+    pub fn register_arbitrator(ctx: Context<RegisterArbitrator>, arbitrator: Pubkey) -> Result<()> {
+        instructions::register_arbitrator::handler(ctx, arbitrator)
+    }
+
+    /// Remove an arbitrator from the registry (admin-only).
+    pub fn deregister_arbitrator(ctx: Context<DeregisterArbitrator>) -> Result<()> {
+        instructions::deregister_arbitrator::handler(ctx)
+    }
+
+    /// Withdraw accumulated protocol fees from a mint's treasury (admin-only).
+    pub fn withdraw_fees<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFees<'info>>, amount: u64) -> Result<()> {
+        instructions::withdraw_fees::handler(ctx, amount)
+    }
+
+    /// Withdraw accumulated native SOL protocol fees (admin-only).
+    pub fn withdraw_sol_fees(ctx: Context<WithdrawSolFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_sol_fees::handler(ctx, amount)
     }
 
     /// Create a new escrow job with funds locked in PDA.
-    pub fn create_job(
-        ctx: Context<CreateJob>,
+    pub fn create_job<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateJob<'info>>,
         job_id: String,
         amount: u64,
         description: String,
         deadline_days: u8,
+        terms: JobTerms,
     ) -> Result<()> {
-        instructions::create_job::handler(ctx, job_id, amount, description, deadline_days)
+        instructions::create_job::handler(ctx, job_id, amount, description, deadline_days, terms)
+    }
+
+    /// Create a new escrow job funded with native SOL.
+    pub fn create_sol_job(
+        ctx: Context<CreateSolJob>,
+        job_id: String,
+        amount: u64,
+        description: String,
+        deadline_days: u8,
+        terms: JobTerms,
+    ) -> Result<()> {
+        instructions::create_sol_job::handler(ctx, job_id, amount, description, deadline_days, terms)
+    }
+
+    /// Requester adds funds to an existing job's escrow.
+    pub fn increase_amount<'info>(
+        ctx: Context<'_, '_, '_, 'info, IncreaseAmount<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::increase_amount::handler(ctx, amount)
     }
 
     /// Agent accepts an open job.
-    pub fn accept_job(ctx: Context<AcceptJob>) -> Result<()> {
+    pub fn accept_job<'info>(ctx: Context<'_, '_, '_, 'info, AcceptJob<'info>>) -> Result<()> {
         instructions::accept_job::handler(ctx)
     }
 
-    /// Agent submits deliverable for review.
+    /// Agent gives up an accepted job, reopening it for other agents.
+    pub fn abandon_job<'info>(ctx: Context<'_, '_, '_, 'info, AbandonJob<'info>>) -> Result<()> {
+        instructions::abandon_job::handler(ctx)
+    }
+
+    /// Agent applies to an open job with a proposed price, ETA and pitch hash.
+    pub fn apply_to_job(
+        ctx: Context<ApplyToJob>,
+        proposed_amount: u64,
+        eta_days: u8,
+        pitch_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::apply_to_job::handler(ctx, proposed_amount, eta_days, pitch_hash)
+    }
+
+    /// Requester selects an applicant and closes the losing applications.
+    pub fn select_agent<'info>(ctx: Context<'_, '_, 'info, 'info, SelectAgent<'info>>) -> Result<()> {
+        instructions::select_agent::handler(ctx)
+    }
+
+    /// Requester releases an agent selected for a bonded job who has not
+    /// posted the bond.
+    pub fn unselect_agent(ctx: Context<UnselectAgent>) -> Result<()> {
+        instructions::unselect_agent::handler(ctx)
+    }
+
+    /// Agent withdraws their application and reclaims its rent.
+    pub fn withdraw_application(ctx: Context<WithdrawApplication>) -> Result<()> {
+        instructions::withdraw_application::handler(ctx)
+    }
+
+    /// Agent submits the current milestone's deliverable for review.
     pub fn submit_deliverable(
         ctx: Context<SubmitDeliverable>,
-        deliverable_url: String,
-        notes: String,
+        milestone_index: u8,
+        deliverable: Deliverable,
+        artifacts: Vec<Deliverable>,
     ) -> Result<()> {
-        instructions::submit_deliverable::handler(ctx, deliverable_url, notes)
+        instructions::submit_deliverable::handler(ctx, milestone_index, deliverable, artifacts)
     }
 
-    /// Requester approves work and releases payment.
-    pub fn approve_and_pay(ctx: Context<ApproveAndPay>, rating: u8) -> Result<()> {
-        instructions::approve_and_pay::handler(ctx, rating)
+    /// Requester or agent proposes a later job deadline.
+    pub fn propose_deadline_extension(
+        ctx: Context<ProposeDeadlineExtension>,
+        new_deadline: i64,
+    ) -> Result<()> {
+        instructions::propose_deadline_extension::handler(ctx, new_deadline)
+    }
+
+    /// The other party accepts the proposed deadline.
+    pub fn accept_deadline_extension(
+        ctx: Context<AcceptDeadlineExtension>,
+        new_deadline: i64,
+    ) -> Result<()> {
+        instructions::accept_deadline_extension::handler(ctx, new_deadline)
+    }
+
+    /// Requester approves the current milestone and releases its payment.
+    pub fn approve_and_pay<'info>(
+        ctx: Context<'_, '_, '_, 'info, ApproveAndPay<'info>>,
+        milestone_index: u8,
+        rating: u8,
+    ) -> Result<()> {
+        instructions::approve_and_pay::handler(ctx, milestone_index, rating)
+    }
+
+    /// Requester sends the current milestone back to the agent for changes.
+    pub fn request_revision(
+        ctx: Context<RequestRevision>,
+        milestone_index: u8,
+        feedback: String,
+    ) -> Result<()> {
+        instructions::request_revision::handler(ctx, milestone_index, feedback)
+    }
+
+    /// Requester rejects the current milestone, opening a dispute.
+    pub fn reject_work(
+        ctx: Context<RejectWork>,
+        milestone_index: u8,
+        reason: String,
+        force_dispute: bool,
+    ) -> Result<()> {
+        instructions::reject_work::handler(ctx, milestone_index, reason, force_dispute)
+    }
+
+    /// Requester or agent attaches a content hash + URI to a dispute.
+    pub fn submit_evidence(
+        ctx: Context<SubmitEvidence>,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        instructions::submit_evidence::handler(ctx, content_hash, uri)
     }
 
-    /// Requester rejects work, opening a dispute.
-    pub fn reject_work(ctx: Context<RejectWork>, reason: String) -> Result<()> {
-        instructions::reject_work::handler(ctx, reason)
+    /// Anyone pays the agent once the review window lapses unanswered.
+    pub fn claim_after_review_timeout<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAfterReviewTimeout<'info>>,
+        milestone_index: u8,
+    ) -> Result<()> {
+        instructions::claim_after_review_timeout::handler(ctx, milestone_index)
     }
 
     /// Requester cancels an open job (refunds escrowed tokens).
-    pub fn cancel_job(ctx: Context<CancelJob>) -> Result<()> {
+    pub fn cancel_job<'info>(ctx: Context<'_, '_, '_, 'info, CancelJob<'info>>) -> Result<()> {
         instructions::cancel_job::handler(ctx)
     }
 
-    /// Authorized arbitrator resolves a dispute.
-    pub fn resolve_dispute(
-        ctx: Context<ResolveDispute>,
+    /// Requester or agent proposes splitting the remaining escrow early.
+    pub fn propose_settlement(
+        ctx: Context<ProposeSettlement>,
+        agent_percentage: u8,
+        bond_agent_percentage: u8,
+    ) -> Result<()> {
+        instructions::propose_settlement::handler(ctx, agent_percentage, bond_agent_percentage)
+    }
+
+    /// The other party accepts the proposed split, which is paid out at once.
+    pub fn accept_settlement<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptSettlement<'info>>,
+        agent_percentage: u8,
+        bond_agent_percentage: u8,
+    ) -> Result<()> {
+        instructions::accept_settlement::handler(ctx, agent_percentage, bond_agent_percentage)
+    }
+
+    /// Panel arbitrator votes on a dispute; the median split is executed
+    /// once the threshold is reached.
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveDispute<'info>>,
         agent_percentage: u8,
+        bond_agent_percentage: u8,
+    ) -> Result<()> {
+        instructions::resolve_dispute::handler(ctx, agent_percentage, bond_agent_percentage)
+    }
+
+    /// Anyone applies the config's fallback split to a dispute left
+    /// unresolved past its deadline.
+    pub fn expire_dispute<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExpireDispute<'info>>,
     ) -> Result<()> {
-        instructions::resolve_dispute::handler(ctx, agent_percentage)
+        instructions::expire_dispute::handler(ctx)
+    }
+
+    /// Agent rates the requester once the job is Completed or Resolved.
+    pub fn rate_requester(ctx: Context<RateRequester>, rating: u8) -> Result<()> {
+        instructions::rate_requester::handler(ctx, rating)
+    }
+
+    /// Requester closes a terminal job, reclaiming rent from the job and
+    /// escrow token accounts. Optionally keeps a compact archive record.
+    pub fn close_job<'info>(ctx: Context<'_, '_, '_, 'info, CloseJob<'info>>) -> Result<()> {
+        instructions::close_job::handler(ctx)
     }
 }
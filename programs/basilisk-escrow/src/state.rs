@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::errors::EscrowError;
 
 // ============================================================================
 // CONSTANTS
@@ -7,11 +9,65 @@ use anchor_lang::prelude::*;
 /// Maximum length for job_id string (UUID format)
 pub const MAX_JOB_ID_LEN: usize = 36;
 
+/// SHA-256 of a job_id, used in place of the raw string in PDA seeds.
+///
+/// A single seed is limited to 32 bytes, which a canonical 36-char UUID
+/// exceeds; hashing gives a fixed-size seed for any job_id.
+pub fn job_id_hash(job_id: &str) -> [u8; 32] {
+    hash(job_id.as_bytes()).to_bytes()
+}
+
 /// Maximum length for job description
 pub const MAX_DESCRIPTION_LEN: usize = 200;
 
-/// Maximum length for deliverable data (URL + notes + rejection reason)
-pub const MAX_DELIVERABLE_LEN: usize = 500;
+/// Maximum length of a deliverable URI
+pub const MAX_DELIVERABLE_URI_LEN: usize = 128;
+
+/// Maximum length of a deliverable's MIME/type tag (e.g. "application/zip")
+pub const MAX_CONTENT_TYPE_LEN: usize = 32;
+
+/// Maximum number of extra artifacts submitted alongside a deliverable
+pub const MAX_ARTIFACTS: usize = 8;
+
+/// Maximum length of a rejection reason or revision feedback
+pub const MAX_FEEDBACK_LEN: usize = 200;
+
+/// Maximum number of milestones a job can be split into
+pub const MAX_MILESTONES: usize = 10;
+
+/// Maximum number of agents a private job can invite
+pub const MAX_ALLOWED_AGENTS: usize = 5;
+
+/// Maximum number of times a job's deadline can be extended
+pub const MAX_DEADLINE_EXTENSIONS: u8 = 3;
+
+/// Review window used when a job does not set its own
+pub const DEFAULT_REVIEW_DAYS: u8 = 7;
+
+/// Dispute window used until the admin configures one
+pub const DEFAULT_DISPUTE_DAYS: u8 = 14;
+
+/// Days the agent has to rate the requester before a settled job can be
+/// closed unrated
+pub const RATING_WINDOW_DAYS: u8 = 7;
+
+/// Maximum number of arbitrators on the dispute panel
+pub const MAX_ARBITRATORS: usize = 5;
+
+/// Maximum number of evidence items each party can attach to a dispute
+pub const MAX_EVIDENCE_ITEMS: usize = 5;
+
+/// Maximum length of an evidence URI
+pub const MAX_EVIDENCE_URI_LEN: usize = 128;
+
+/// Maximum number of mints an agent profile tracks volume for
+pub const MAX_PROFILE_MINTS: usize = 8;
+
+/// Basis-point denominator (10_000 bps = 100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Maximum protocol fee the admin can configure (10%)
+pub const MAX_FEE_BPS: u16 = 1_000;
 
 // ============================================================================
 // PROGRAM CONFIG - Global configuration PDA
@@ -21,17 +77,113 @@ pub const MAX_DELIVERABLE_LEN: usize = 500;
 pub struct ProgramConfig {
     /// Admin who can update configuration
     pub admin: Pubkey,
-    /// Authorized arbitrator for dispute resolution
-    pub arbitrator: Pubkey,
+    /// Arbitrator panel for dispute resolution (max MAX_ARBITRATORS)
+    pub arbitrators: Vec<Pubkey>,
+    /// Votes needed before a dispute split is executed
+    pub threshold: u8,
+    /// Only registered arbitrators may be named on a job
+    pub require_registered_arbitrator: bool,
+    /// Default arbitration fee for new jobs
+    pub arbitration_fee: ArbitrationFee,
+    /// Dispute window and the split applied when it lapses unresolved
+    pub dispute_fallback: DisputeFallback,
+    /// Protocol fee charged on payouts, in basis points (max MAX_FEE_BPS)
+    pub fee_bps: u16,
     /// PDA bump seed
     pub bump: u8,
 }
 
 impl ProgramConfig {
-    /// Discriminator (8) + admin (32) + arbitrator (32) + bump (1) = 73
+    /// admin (32) + arbitrators (4 + 32 * MAX_ARBITRATORS = 164) + threshold (1)
+    /// + require_registered_arbitrator (1) + arbitration_fee (10)
+    /// + dispute_fallback (3) + fee_bps (2) + bump (1) = 214
+    pub const LEN: usize = 32
+        + (4 + 32 * MAX_ARBITRATORS)
+        + 1
+        + 1
+        + ArbitrationFee::LEN
+        + DisputeFallback::LEN
+        + 2
+        + 1;
+
+    /// Replace the arbitrator panel and threshold after validating them.
+    pub fn set_arbitrators(&mut self, arbitrators: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(
+            arbitrators.len() <= MAX_ARBITRATORS,
+            EscrowError::TooManyArbitrators
+        );
+        require!(
+            threshold >= 1 && threshold as usize <= arbitrators.len(),
+            EscrowError::InvalidThreshold
+        );
+        for (i, arbitrator) in arbitrators.iter().enumerate() {
+            require!(
+                *arbitrator != Pubkey::default() && !arbitrators[..i].contains(arbitrator),
+                EscrowError::InvalidArbitrator
+            );
+        }
+        self.arbitrators = arbitrators;
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    pub fn is_arbitrator(&self, key: &Pubkey) -> bool {
+        self.arbitrators.contains(key)
+    }
+
+    /// Check the arbitrator named on a new job against the registry, when
+    /// the admin has restricted job arbitrators to registered ones.
+    pub fn check_job_arbitrator(
+        &self,
+        arbitrator: &Pubkey,
+        registration: Option<&ArbitratorRegistration>,
+    ) -> Result<()> {
+        if *arbitrator == Pubkey::default() || !self.require_registered_arbitrator {
+            return Ok(());
+        }
+        let registration = registration.ok_or(EscrowError::MissingAccount)?;
+        require!(
+            registration.arbitrator == *arbitrator,
+            EscrowError::ArbitratorNotRegistered
+        );
+        Ok(())
+    }
+}
+
+/// ProgramConfig as written by the original single-arbitrator program, read
+/// once by migrate_config. Shares ProgramConfig's discriminator.
+#[derive(AnchorDeserialize)]
+pub struct LegacyProgramConfig {
+    /// Admin who can update configuration
+    pub admin: Pubkey,
+    /// Sole arbitrator for dispute resolution
+    pub arbitrator: Pubkey,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl LegacyProgramConfig {
+    /// admin (32) + arbitrator (32) + bump (1) = 65
     pub const LEN: usize = 32 + 32 + 1;
 }
 
+/// Registry entry for an arbitrator that jobs may name, maintained by the
+/// admin via register_arbitrator / deregister_arbitrator.
+#[account]
+pub struct ArbitratorRegistration {
+    /// Registered arbitrator
+    pub arbitrator: Pubkey,
+    /// Unix timestamp when the arbitrator was registered
+    pub registered_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ArbitratorRegistration {
+    /// arbitrator (32) + registered_at (8) + bump (1) = 41
+    pub const LEN: usize = 32 + 8 + 1;
+}
+
 // ============================================================================
 // JOB - Per-job escrow state PDA
 // ============================================================================
@@ -40,6 +192,8 @@ impl ProgramConfig {
 pub struct Job {
     /// Unique job identifier (max 36 chars, UUID format)
     pub job_id: String,
+    /// SHA-256 of job_id; the per-job PDA seed (see `job_id_hash`)
+    pub job_id_hash: [u8; 32],
     /// Requester who posted and funded the job
     pub requester: Pubkey,
     /// Agent assigned to the job (Pubkey::default() if unassigned)
@@ -54,11 +208,11 @@ pub struct Job {
     pub created_at: i64,
     /// Unix timestamp deadline for job completion
     pub deadline: i64,
-    /// Deliverable data: URL + notes (max 500 chars)
-    pub deliverable: String,
+    /// Latest submitted deliverable (empty until the first submission)
+    pub deliverable: Deliverable,
     /// Whether the job is/was in dispute
     pub disputed: bool,
-    /// Rating given by requester (1-5, 0 = unrated)
+    /// Rating given by requester with the final milestone (1-5, 0 = unrated)
     pub rating: u8,
     /// Job PDA bump seed
     pub bump: u8,
@@ -66,8 +220,69 @@ pub struct Job {
     pub escrow_authority_bump: u8,
     /// Escrow token account PDA bump seed
     pub escrow_token_bump: u8,
-    /// Token mint for this job's escrow
+    /// Token mint for this job's escrow (Pubkey::default() for native SOL)
     pub mint: Pubkey,
+    /// Protocol fee in basis points, snapshotted from ProgramConfig at creation
+    pub fee_bps: u16,
+    /// Kind of asset held in escrow (SPL token or native SOL)
+    pub asset: AssetKind,
+    /// SOL vault PDA bump seed (native jobs only, 0 otherwise)
+    pub vault_bump: u8,
+    /// Payment schedule; amounts sum to `amount` (max MAX_MILESTONES)
+    pub milestones: Vec<Milestone>,
+    /// Index of the milestone currently being worked on / reviewed
+    pub current_milestone: u8,
+    /// Total escrow already released for paid-out milestones
+    pub released_amount: u64,
+    /// Agents invited to a private job (empty = anyone may accept)
+    pub allowed_agents: Vec<Pubkey>,
+    /// Bond the agent must deposit on acceptance (0 = no bond); set from
+    /// `bond_bps` on acceptance for percentage bonds
+    pub bond_amount: u64,
+    /// Bond currently held in the bond escrow (net of transfer fees)
+    pub bond_balance: u64,
+    /// Bond escrow PDA bump seed (bond_token or bond_vault, 0 if no bond)
+    pub bond_bump: u8,
+    /// Rating given by agent to the requester (1-5, 0 = unrated)
+    pub requester_rating: u8,
+    /// Arbitrator named for this job (Pubkey::default() = config panel)
+    pub arbitrator: Pubkey,
+    /// Arbitration fee charged on dispute resolution (snapshotted at creation)
+    pub arbitration_fee: ArbitrationFee,
+    /// Seconds the requester has to review a submission before the agent
+    /// can claim payment
+    pub review_period: i64,
+    /// Unix timestamp of the latest deliverable submission
+    pub submitted_at: i64,
+    /// Unix timestamp after which an open dispute can be expired to the
+    /// config's fallback split (0 = no dispute opened)
+    pub dispute_deadline: i64,
+    /// Party with a pending deadline extension (Pubkey::default() = none)
+    pub extension_proposer: Pubkey,
+    /// Job deadline proposed by `extension_proposer`
+    pub proposed_deadline: i64,
+    /// Deadline extensions agreed so far (max MAX_DEADLINE_EXTENSIONS)
+    pub extensions: u8,
+    /// Party with a pending settlement proposal (Pubkey::default() = none)
+    pub settlement_proposer: Pubkey,
+    /// Proposed agent share (0-100) of the remaining escrow
+    pub settlement_agent_percentage: u8,
+    /// Proposed agent share (0-100) of their bond
+    pub settlement_bond_agent_percentage: u8,
+    /// Revisions the requester may ask for before work must be approved or
+    /// disputed
+    pub max_revisions: u8,
+    /// Revisions requested so far
+    pub revisions: u8,
+    /// Extra artifacts recorded in the job's DeliverableArtifacts account
+    /// for the latest submission (0 = the deliverable stands alone)
+    pub artifact_count: u8,
+    /// Bond as basis points of `amount`, resolved into `bond_amount` on
+    /// acceptance (0 = `bond_amount` is a fixed amount)
+    pub bond_bps: u16,
+    /// Unix timestamp when the job became Completed or Resolved (0 = not
+    /// settled); starts the agent's window to rate the requester
+    pub settled_at: i64,
 }
 
 impl Job {
@@ -77,6 +292,7 @@ impl Job {
     ///
     /// Fields:
     ///   job_id:                4 + MAX_JOB_ID_LEN      = 40
+    ///   job_id_hash:           32
     ///   requester:             32
     ///   agent:                 32
     ///   amount:                8
@@ -84,16 +300,44 @@ impl Job {
     ///   status (enum):         1
     ///   created_at:            8
     ///   deadline:              8
-    ///   deliverable:           4 + MAX_DELIVERABLE_LEN  = 504
+    ///   deliverable:           200
     ///   disputed:              1
     ///   rating:                1
     ///   bump:                  1
     ///   escrow_authority_bump: 1
     ///   escrow_token_bump:     1
     ///   mint:                  32
+    ///   fee_bps:               2
+    ///   asset (enum):          1
+    ///   vault_bump:            1
+    ///   milestones:            4 + MAX_MILESTONES * 49 = 494
+    ///   current_milestone:     1
+    ///   released_amount:       8
+    ///   allowed_agents:        4 + MAX_ALLOWED_AGENTS * 32 = 164
+    ///   bond_amount:           8
+    ///   bond_balance:          8
+    ///   bond_bump:             1
+    ///   requester_rating:      1
+    ///   arbitrator:            32
+    ///   arbitration_fee:       10
+    ///   review_period:         8
+    ///   submitted_at:          8
+    ///   dispute_deadline:      8
+    ///   extension_proposer:    32
+    ///   proposed_deadline:     8
+    ///   extensions:            1
+    ///   settlement_proposer:   32
+    ///   settlement_agent_percentage:      1
+    ///   settlement_bond_agent_percentage: 1
+    ///   max_revisions:         1
+    ///   revisions:             1
+    ///   artifact_count:        1
+    ///   bond_bps:              2
+    ///   settled_at:            8
     ///   -----------------------------------------
-    ///   Total:                 874
+    ///   Total:                 1445
     pub const LEN: usize = (4 + MAX_JOB_ID_LEN)
+        + 32  // job_id_hash
         + 32  // requester
         + 32  // agent
         + 8   // amount
@@ -101,13 +345,1191 @@ impl Job {
         + 1   // status
         + 8   // created_at
         + 8   // deadline
-        + (4 + MAX_DELIVERABLE_LEN)
+        + Deliverable::LEN
         + 1   // disputed
         + 1   // rating
         + 1   // bump
         + 1   // escrow_authority_bump
         + 1   // escrow_token_bump
-        + 32; // mint
+        + 32  // mint
+        + 2   // fee_bps
+        + 1   // asset
+        + 1   // vault_bump
+        + (4 + MAX_MILESTONES * Milestone::LEN)
+        + 1   // current_milestone
+        + 8   // released_amount
+        + (4 + MAX_ALLOWED_AGENTS * 32)
+        + 8   // bond_amount
+        + 8   // bond_balance
+        + 1   // bond_bump
+        + 1   // requester_rating
+        + 32  // arbitrator
+        + ArbitrationFee::LEN
+        + 8   // review_period
+        + 8   // submitted_at
+        + 8   // dispute_deadline
+        + 32  // extension_proposer
+        + 8   // proposed_deadline
+        + 1   // extensions
+        + 32  // settlement_proposer
+        + 1   // settlement_agent_percentage
+        + 1   // settlement_bond_agent_percentage
+        + 1   // max_revisions
+        + 1   // revisions
+        + 1   // artifact_count
+        + 2   // bond_bps
+        + 8;  // settled_at
+
+    /// Validate creation inputs and populate the fields shared by every kind
+    /// of job. Callers set the PDA bumps, mint and asset kind afterwards.
+    ///
+    /// An empty `terms.milestones` list creates a single milestone covering
+    /// the whole amount, which behaves exactly like a plain one-shot job.
+    #[allow(clippy::too_many_arguments)]
+    pub fn open(
+        &mut self,
+        job_id: String,
+        requester: Pubkey,
+        amount: u64,
+        description: String,
+        deadline_days: u8,
+        terms: JobTerms,
+        config: &ProgramConfig,
+    ) -> Result<()> {
+        // ── Input validation ────────────────────────────────────────────
+        require!(job_id.len() <= MAX_JOB_ID_LEN, EscrowError::JobIdTooLong);
+        require!(
+            description.len() <= MAX_DESCRIPTION_LEN,
+            EscrowError::DescriptionTooLong
+        );
+        require!(amount > 0, EscrowError::ZeroAmount);
+        require!(deadline_days > 0, EscrowError::InvalidDeadline);
+
+        let clock = Clock::get()?;
+
+        self.job_id_hash = job_id_hash(&job_id);
+        self.job_id = job_id;
+        self.requester = requester;
+        self.agent = Pubkey::default(); // Not assigned yet
+        self.amount = amount;
+        self.description = description;
+        self.status = JobStatus::Open;
+        self.created_at = clock.unix_timestamp;
+        self.deadline = clock
+            .unix_timestamp
+            .checked_add((deadline_days as i64).checked_mul(86400).ok_or(EscrowError::Overflow)?)
+            .ok_or(EscrowError::Overflow)?;
+        self.deliverable = Deliverable::default();
+        self.disputed = false;
+        self.rating = 0;
+        self.fee_bps = config.fee_bps;
+        self.current_milestone = 0;
+        self.released_amount = 0;
+        self.milestones = self.build_milestones(amount, terms.milestones)?;
+        self.set_allowed_agents(terms.allowed_agents)?;
+        require!(
+            (terms.bond_amount == 0 || terms.bond_bps == 0)
+                && terms.bond_bps as u64 <= BPS_DENOMINATOR,
+            EscrowError::InvalidBond
+        );
+        self.bond_amount = terms.bond_amount;
+        self.bond_bps = terms.bond_bps;
+        self.bond_balance = 0;
+        self.bond_bump = 0;
+        self.requester_rating = 0;
+        self.arbitrator = terms.arbitrator.unwrap_or_default();
+        require!(self.arbitrator != requester, EscrowError::InvalidArbitrator);
+        self.arbitration_fee = match terms.arbitration_fee {
+            Some(fee) => {
+                fee.validate()?;
+                fee
+            }
+            None => config.arbitration_fee,
+        };
+        let review_days = terms.review_days.unwrap_or(DEFAULT_REVIEW_DAYS);
+        require!(review_days > 0, EscrowError::InvalidReviewWindow);
+        self.review_period = (review_days as i64)
+            .checked_mul(86400)
+            .ok_or(EscrowError::Overflow)?;
+        self.submitted_at = 0;
+        self.dispute_deadline = 0;
+        self.extension_proposer = Pubkey::default();
+        self.proposed_deadline = 0;
+        self.extensions = 0;
+        self.settlement_proposer = Pubkey::default();
+        self.settlement_agent_percentage = 0;
+        self.settlement_bond_agent_percentage = 0;
+        self.max_revisions = terms.max_revisions;
+        self.revisions = 0;
+        self.artifact_count = 0;
+        self.settled_at = 0;
+        Ok(())
+    }
+
+    /// Turn the requested payment schedule into stored milestones.
+    fn build_milestones(
+        &self,
+        amount: u64,
+        params: Vec<MilestoneParams>,
+    ) -> Result<Vec<Milestone>> {
+        if params.is_empty() {
+            return Ok(vec![Milestone {
+                amount,
+                description_hash: [0; 32],
+                deadline: 0,
+                released: false,
+            }]);
+        }
+        require!(
+            params.len() <= MAX_MILESTONES,
+            EscrowError::TooManyMilestones
+        );
+
+        let mut total: u64 = 0;
+        let mut milestones = Vec::with_capacity(params.len());
+        for param in params {
+            require!(param.amount > 0, EscrowError::ZeroAmount);
+            total = total
+                .checked_add(param.amount)
+                .ok_or(EscrowError::Overflow)?;
+
+            let deadline = match param.deadline_days {
+                Some(days) => {
+                    require!(days > 0, EscrowError::InvalidMilestoneDeadline);
+                    let deadline = self
+                        .created_at
+                        .checked_add((days as i64).checked_mul(86400).ok_or(EscrowError::Overflow)?)
+                        .ok_or(EscrowError::Overflow)?;
+                    require!(
+                        deadline <= self.deadline,
+                        EscrowError::InvalidMilestoneDeadline
+                    );
+                    deadline
+                }
+                None => 0,
+            };
+
+            milestones.push(Milestone {
+                amount: param.amount,
+                description_hash: param.description_hash,
+                deadline,
+                released: false,
+            });
+        }
+        require!(total == amount, EscrowError::MilestoneAmountMismatch);
+        Ok(milestones)
+    }
+
+    /// Restrict who may accept the job. An empty list leaves the job open to
+    /// any agent; otherwise only the listed agents can accept it.
+    fn set_allowed_agents(&mut self, agents: Vec<Pubkey>) -> Result<()> {
+        require!(
+            agents.len() <= MAX_ALLOWED_AGENTS,
+            EscrowError::TooManyAllowedAgents
+        );
+        require!(
+            !agents.contains(&Pubkey::default()),
+            EscrowError::InvalidAgent
+        );
+        self.allowed_agents = agents;
+        Ok(())
+    }
+
+    /// Whether the agent must post a bond, and so whether the job has a bond
+    /// escrow.
+    pub fn is_bonded(&self) -> bool {
+        self.bond_amount > 0 || self.bond_bps > 0
+    }
+
+    /// Bond the agent must post to accept the job now: the fixed
+    /// `bond_amount`, or `bond_bps` of the escrowed amount.
+    pub fn required_bond(&self) -> Result<u64> {
+        if self.bond_bps == 0 {
+            return Ok(self.bond_amount);
+        }
+        let bond = (self.amount as u128)
+            .checked_mul(self.bond_bps as u128)
+            .ok_or(EscrowError::Overflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(bond as u64)
+    }
+
+    /// Whether `agent` may accept this job. The job's own arbitrator can
+    /// never work on it.
+    pub fn is_agent_allowed(&self, agent: &Pubkey) -> bool {
+        *agent != self.arbitrator
+            && (self.allowed_agents.is_empty() || self.allowed_agents.contains(agent))
+    }
+
+    /// Whether `key` may vote on this job's dispute: the arbitrator named at
+    /// creation if there is one, otherwise any member of the config panel.
+    pub fn is_arbitrator(&self, config: &ProgramConfig, key: &Pubkey) -> bool {
+        if self.arbitrator == Pubkey::default() {
+            config.is_arbitrator(key)
+        } else {
+            *key == self.arbitrator
+        }
+    }
+
+    /// Votes needed to resolve this job's dispute.
+    pub fn arbitration_threshold(&self, config: &ProgramConfig) -> u8 {
+        if self.arbitrator == Pubkey::default() {
+            config.threshold
+        } else {
+            1
+        }
+    }
+
+    /// Record the amount actually escrowed when it is less than requested
+    /// (Token-2022 transfer fees). The shortfall comes out of the final
+    /// milestone so the schedule still sums to `amount`.
+    pub fn set_received_amount(&mut self, received: u64) -> Result<()> {
+        let shortfall = self
+            .amount
+            .checked_sub(received)
+            .ok_or(EscrowError::Overflow)?;
+        let last = self
+            .milestones
+            .last_mut()
+            .ok_or(EscrowError::InvalidMilestone)?;
+        last.amount = last
+            .amount
+            .checked_sub(shortfall)
+            .ok_or(EscrowError::ZeroAmount)?;
+        require!(last.amount > 0, EscrowError::ZeroAmount);
+        self.amount = received;
+        Ok(())
+    }
+
+    /// Add a top-up of `added` to the escrow. It goes to the final milestone,
+    /// which is never released before the job completes, so the schedule
+    /// still sums to `amount`. Pending proposals are dropped.
+    pub fn increase_amount(&mut self, added: u64) -> Result<()> {
+        let last = self
+            .milestones
+            .last_mut()
+            .ok_or(EscrowError::InvalidMilestone)?;
+        last.amount = last
+            .amount
+            .checked_add(added)
+            .ok_or(EscrowError::Overflow)?;
+        self.amount = self
+            .amount
+            .checked_add(added)
+            .ok_or(EscrowError::Overflow)?;
+        self.clear_proposals();
+        Ok(())
+    }
+
+    /// Drop any pending settlement and deadline extension. A settlement is a
+    /// percentage of whatever escrow is unreleased when it is accepted, so
+    /// both are only valid against the status and balance they were
+    /// proposed on; every instruction that changes either calls this.
+    pub fn clear_proposals(&mut self) {
+        self.extension_proposer = Pubkey::default();
+        self.proposed_deadline = 0;
+        self.settlement_proposer = Pubkey::default();
+        self.settlement_agent_percentage = 0;
+        self.settlement_bond_agent_percentage = 0;
+    }
+
+    /// The milestone currently in progress, checked against the index the
+    /// caller claims to be acting on.
+    pub fn milestone(&self, index: u8) -> Result<&Milestone> {
+        require!(
+            index == self.current_milestone,
+            EscrowError::InvalidMilestone
+        );
+        self.milestones
+            .get(index as usize)
+            .ok_or_else(|| error!(EscrowError::InvalidMilestone))
+    }
+
+    /// Deadline for the current milestone, falling back to the job deadline
+    /// when the milestone has none of its own.
+    pub fn current_deadline(&self) -> i64 {
+        self.milestones
+            .get(self.current_milestone as usize)
+            .map(|m| m.deadline)
+            .filter(|deadline| *deadline > 0)
+            .unwrap_or(self.deadline)
+    }
+
+    /// Move the job deadline to `new_deadline`, pushing every milestone
+    /// deadline back by the same amount so the schedule keeps its shape.
+    pub fn extend_deadline(&mut self, new_deadline: i64) -> Result<()> {
+        let delta = new_deadline
+            .checked_sub(self.deadline)
+            .filter(|delta| *delta > 0)
+            .ok_or(EscrowError::InvalidExtension)?;
+        for milestone in self.milestones.iter_mut().filter(|m| m.deadline > 0) {
+            milestone.deadline = milestone
+                .deadline
+                .checked_add(delta)
+                .ok_or(EscrowError::Overflow)?;
+        }
+        self.deadline = new_deadline;
+        Ok(())
+    }
+
+    /// Leave the agent at least one review period from `now` to resubmit:
+    /// the job deadline and the deadlines of the current and later
+    /// milestones are pushed back to `now + review_period` where they would
+    /// end sooner. Returns the deadline now in force for the current
+    /// milestone.
+    pub fn extend_for_resubmission(&mut self, now: i64) -> Result<i64> {
+        let resubmit_by = now
+            .checked_add(self.review_period)
+            .ok_or(EscrowError::Overflow)?;
+        let current = self.current_milestone as usize;
+        for milestone in self
+            .milestones
+            .iter_mut()
+            .skip(current)
+            .filter(|m| m.deadline > 0)
+        {
+            milestone.deadline = milestone.deadline.max(resubmit_by);
+        }
+        self.deadline = self.deadline.max(resubmit_by);
+        Ok(self.current_deadline())
+    }
+
+    /// Escrowed funds not yet released to the agent.
+    pub fn remaining_amount(&self) -> Result<u64> {
+        self.amount
+            .checked_sub(self.released_amount)
+            .ok_or_else(|| error!(EscrowError::Overflow))
+    }
+
+    /// Whether the current milestone is the final one.
+    pub fn is_last_milestone(&self) -> bool {
+        self.current_milestone as usize + 1 >= self.milestones.len()
+    }
+
+    /// Unix timestamp after which an unanswered submission can be claimed.
+    pub fn review_deadline(&self) -> Result<i64> {
+        self.submitted_at
+            .checked_add(self.review_period)
+            .ok_or_else(|| error!(EscrowError::Overflow))
+    }
+
+    /// Book the payout of the current milestone after `bond_returned` has
+    /// been paid back to the agent. The job is Completed after the final
+    /// milestone, otherwise it goes back to InProgress for the next one.
+    /// Pending proposals are dropped. Returns true once the job is Completed.
+    pub fn settle_milestone(&mut self, bond_returned: u64) -> Result<bool> {
+        self.bond_balance = self
+            .bond_balance
+            .checked_sub(bond_returned)
+            .ok_or(EscrowError::Overflow)?;
+        let completed = self.release_milestone()?;
+        self.clear_proposals();
+        if completed {
+            self.status = JobStatus::Completed;
+            self.settled_at = Clock::get()?.unix_timestamp;
+        } else {
+            self.status = JobStatus::InProgress;
+        }
+        Ok(completed)
+    }
+
+    /// Whether the agent may still rate the requester, which keeps the job
+    /// from being closed: it was Completed or Resolved less than
+    /// RATING_WINDOW_DAYS ago and the agent has not rated yet.
+    pub fn awaiting_requester_rating(&self, now: i64) -> Result<bool> {
+        let window_end = self
+            .settled_at
+            .checked_add(RATING_WINDOW_DAYS as i64 * 86400)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(matches!(self.status, JobStatus::Completed | JobStatus::Resolved)
+            && self.requester_rating == 0
+            && now <= window_end)
+    }
+
+    /// Mark the current milestone paid. Returns true once every milestone
+    /// has been released, otherwise advances to the next milestone.
+    pub fn release_milestone(&mut self) -> Result<bool> {
+        let index = self.current_milestone as usize;
+        let milestone = self
+            .milestones
+            .get_mut(index)
+            .ok_or(EscrowError::InvalidMilestone)?;
+        milestone.released = true;
+        self.released_amount = self
+            .released_amount
+            .checked_add(milestone.amount)
+            .ok_or(EscrowError::Overflow)?;
+
+        if self.is_last_milestone() {
+            return Ok(true);
+        }
+        self.current_milestone = self
+            .current_milestone
+            .checked_add(1)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(false)
+    }
+
+    /// Split the held bond between agent and requester by percentage.
+    /// Returns (agent_share, requester_share).
+    pub fn split_bond(&self, agent_percentage: u8) -> Result<(u64, u64)> {
+        let agent_share = (self.bond_balance as u128)
+            .checked_mul(agent_percentage as u128)
+            .ok_or(EscrowError::Overflow)?
+            .checked_div(100)
+            .ok_or(EscrowError::Overflow)? as u64;
+        let requester_share = self
+            .bond_balance
+            .checked_sub(agent_share)
+            .ok_or(EscrowError::Overflow)?;
+        Ok((agent_share, requester_share))
+    }
+
+    /// Signer seeds for the job's escrow authority PDA. The PDA is namespaced
+    /// by requester so a job_id cannot be squatted by someone else.
+    pub fn escrow_authority_seeds(&self) -> [&[u8]; 4] {
+        [
+            b"escrow",
+            self.requester.as_ref(),
+            self.job_id_hash.as_ref(),
+            std::slice::from_ref(&self.escrow_authority_bump),
+        ]
+    }
+
+    /// Protocol fee owed on a payout of `gross` tokens to the agent.
+    /// Uses a u128 intermediate so the multiplication cannot overflow.
+    pub fn protocol_fee(&self, gross: u64) -> Result<u64> {
+        let fee = (gross as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(EscrowError::Overflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(fee as u64)
+    }
+
+    /// Arbitration fee owed on resolving a dispute over `disputed` tokens,
+    /// and how much of it comes out of the agent's and the requester's
+    /// awarded shares. A share that cannot cover its part of the fee passes
+    /// the rest to the other party; the fee never exceeds `disputed`.
+    pub fn arbitration_fee_shares(
+        &self,
+        disputed: u64,
+        agent_amount: u64,
+        agent_percentage: u8,
+    ) -> Result<(u64, u64)> {
+        let fee = self.arbitration_fee.amount(disputed)?;
+        let agent_weight = match self.arbitration_fee.bearer {
+            // Each party pays in proportion to the share they lost
+            FeeBearer::LoserPays => 100 - agent_percentage as u128,
+            FeeBearer::Split => 50,
+        };
+        let mut agent_fee = (fee as u128)
+            .checked_mul(agent_weight)
+            .ok_or(EscrowError::Overflow)?
+            .checked_div(100)
+            .ok_or(EscrowError::Overflow)? as u64;
+        agent_fee = agent_fee.min(agent_amount);
+        let requester_amount = disputed
+            .checked_sub(agent_amount)
+            .ok_or(EscrowError::Overflow)?;
+        let mut requester_fee = fee.checked_sub(agent_fee).ok_or(EscrowError::Overflow)?;
+        if requester_fee > requester_amount {
+            agent_fee = agent_fee
+                .checked_add(requester_fee - requester_amount)
+                .ok_or(EscrowError::Overflow)?;
+            requester_fee = requester_amount;
+        }
+        Ok((agent_fee, requester_fee))
+    }
+
+    /// Divide the escrow not yet released, settling the whole job:
+    /// `agent_percentage` to the agent and the rest to the requester, and
+    /// the bond by `bond_agent_percentage`. The protocol fee applies to the
+    /// agent's share only.
+    pub fn split_remaining(
+        &self,
+        agent_percentage: u8,
+        bond_agent_percentage: u8,
+    ) -> Result<EscrowSplit> {
+        self.split(
+            self.remaining_amount()?,
+            agent_percentage,
+            Some(bond_agent_percentage),
+            false,
+        )
+    }
+
+    /// Divide the escrow of the disputed current milestone by
+    /// `agent_percentage`; later milestones stay in escrow. The bond is
+    /// only split by `bond_agent_percentage` when the final milestone is
+    /// disputed, otherwise it stays held for the rest of the job. With
+    /// `with_arbitration_fee` the job's arbitration fee is first taken out
+    /// of both awards; the protocol fee then applies to the agent's
+    /// remaining share only.
+    pub fn split_disputed(
+        &self,
+        agent_percentage: u8,
+        bond_agent_percentage: u8,
+        with_arbitration_fee: bool,
+    ) -> Result<EscrowSplit> {
+        let disputed = self
+            .milestones
+            .get(self.current_milestone as usize)
+            .ok_or(EscrowError::InvalidMilestone)?
+            .amount;
+        let last = self.is_last_milestone();
+        self.split(
+            disputed,
+            agent_percentage,
+            last.then_some(bond_agent_percentage),
+            with_arbitration_fee,
+        )
+    }
+
+    /// Divide `amount` tokens of the escrow between the parties. The bond
+    /// is split only with a `bond_agent_percentage`, which also marks the
+    /// split as settling the whole job.
+    fn split(
+        &self,
+        amount: u64,
+        agent_percentage: u8,
+        bond_agent_percentage: Option<u8>,
+        with_arbitration_fee: bool,
+    ) -> Result<EscrowSplit> {
+        let agent_amount = (amount as u128)
+            .checked_mul(agent_percentage as u128)
+            .ok_or(EscrowError::Overflow)?
+            .checked_div(100)
+            .ok_or(EscrowError::Overflow)? as u64;
+        let requester_amount = amount
+            .checked_sub(agent_amount)
+            .ok_or(EscrowError::Overflow)?;
+
+        let (agent_arbitration_fee, requester_arbitration_fee) = if with_arbitration_fee {
+            self.arbitration_fee_shares(amount, agent_amount, agent_percentage)?
+        } else {
+            (0, 0)
+        };
+        let arbitration_fee = agent_arbitration_fee
+            .checked_add(requester_arbitration_fee)
+            .ok_or(EscrowError::Overflow)?;
+        let agent_amount = agent_amount
+            .checked_sub(agent_arbitration_fee)
+            .ok_or(EscrowError::Overflow)?;
+        let requester_amount = requester_amount
+            .checked_sub(requester_arbitration_fee)
+            .ok_or(EscrowError::Overflow)?;
+
+        let fee = self.protocol_fee(agent_amount)?;
+        let agent_payout = agent_amount
+            .checked_sub(fee)
+            .ok_or(EscrowError::Overflow)?;
+        let (bond_agent_amount, bond_requester_amount) = match bond_agent_percentage {
+            Some(percentage) => self.split_bond(percentage)?,
+            None => (0, 0),
+        };
+
+        Ok(EscrowSplit {
+            ends_job: bond_agent_percentage.is_some(),
+            agent_percentage,
+            agent_payout,
+            requester_amount,
+            fee,
+            arbitration_fee,
+            bond_agent_amount,
+            bond_requester_amount,
+        })
+    }
+}
+
+// ============================================================================
+// JOB TERMS - Optional parameters for job creation
+// ============================================================================
+
+/// Optional terms supplied to create_job / create_sol_job.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq, Debug)]
+pub struct JobTerms {
+    /// Payment schedule (empty = one milestone for the full amount)
+    pub milestones: Vec<MilestoneParams>,
+    /// Agents invited to a private job (empty = anyone may accept)
+    pub allowed_agents: Vec<Pubkey>,
+    /// Bond the agent must deposit on acceptance (0 = no bond)
+    pub bond_amount: u64,
+    /// Bond as basis points of the escrowed amount instead (0 = use
+    /// bond_amount; at most one of the two may be set)
+    pub bond_bps: u16,
+    /// Arbitrator for this job's disputes (None = config panel)
+    pub arbitrator: Option<Pubkey>,
+    /// Arbitration fee for this job (None = config default)
+    pub arbitration_fee: Option<ArbitrationFee>,
+    /// Days the requester has to review each submission
+    /// (None = DEFAULT_REVIEW_DAYS)
+    pub review_days: Option<u8>,
+    /// Revisions the requester may ask for via request_revision
+    pub max_revisions: u8,
+}
+
+// ============================================================================
+// MILESTONES - Staged release of a job's escrow
+// ============================================================================
+
+/// One stage of a job's payment schedule.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Milestone {
+    /// Portion of the escrow released when this milestone is approved
+    pub amount: u64,
+    /// Hash of the off-chain milestone description
+    pub description_hash: [u8; 32],
+    /// Unix timestamp deadline for this milestone (0 = job deadline applies)
+    pub deadline: i64,
+    /// Whether this milestone has been paid out (approved, or split by a
+    /// dispute)
+    pub released: bool,
+}
+
+impl Milestone {
+    /// amount (8) + description_hash (32) + deadline (8) + released (1) = 49
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+}
+
+/// Milestone as supplied to create_job / create_sol_job.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MilestoneParams {
+    /// Portion of the escrow released when this milestone is approved
+    pub amount: u64,
+    /// Hash of the off-chain milestone description
+    pub description_hash: [u8; 32],
+    /// Days from creation until this milestone is due (None = job deadline)
+    pub deadline_days: Option<u8>,
+}
+
+// ============================================================================
+// APPLICATION - An agent's bid on an open job
+// ============================================================================
+
+#[account]
+pub struct Application {
+    /// Job being applied to
+    pub job: Pubkey,
+    /// The job's created_at when the application was made, tying it to this
+    /// job rather than a later one re-created at the same address
+    pub job_created_at: i64,
+    /// Applying agent (paid the rent, receives it back on close)
+    pub agent: Pubkey,
+    /// Price the agent proposes, in the job's token base units (informational)
+    pub proposed_amount: u64,
+    /// Days the agent expects to need
+    pub eta_days: u8,
+    /// Hash of the off-chain pitch
+    pub pitch_hash: [u8; 32],
+    /// Unix timestamp when the application was made
+    pub created_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Application {
+    /// job (32) + job_created_at (8) + agent (32) + proposed_amount (8)
+    /// + eta_days (1) + pitch_hash (32) + created_at (8) + bump (1) = 122
+    pub const LEN: usize = 32 + 8 + 32 + 8 + 1 + 32 + 8 + 1;
+}
+
+// ============================================================================
+// DISPUTE VOTE - Arbitrator panel votes on one disputed job
+// ============================================================================
+
+#[account]
+pub struct DisputeVote {
+    /// Disputed job
+    pub job: Pubkey,
+    /// First voter, who paid the rent and gets it back when the record is
+    /// closed
+    pub payer: Pubkey,
+    /// Votes cast so far, one per arbitrator
+    pub votes: Vec<ArbitratorVote>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl DisputeVote {
+    /// job (32) + payer (32) + votes (4 + 34 * MAX_ARBITRATORS = 174)
+    /// + bump (1) = 239
+    pub const LEN: usize = 32 + 32 + (4 + ArbitratorVote::LEN * MAX_ARBITRATORS) + 1;
+
+    /// Votes of arbitrators still entitled to decide the job, in the order
+    /// they were cast. Only these count towards the outcome and share the
+    /// arbitration fee.
+    pub fn counted_votes<'a>(
+        &'a self,
+        job: &'a Job,
+        config: &'a ProgramConfig,
+    ) -> impl Iterator<Item = &'a ArbitratorVote> + Clone {
+        self.votes
+            .iter()
+            .filter(move |v| job.is_arbitrator(config, &v.arbitrator))
+    }
+
+    /// Median (agent_percentage, bond_agent_percentage) over the counted
+    /// votes, or None while fewer than the job's threshold have voted. With
+    /// an even number of votes the lower median is used, so ties favour the
+    /// requester.
+    pub fn outcome(&self, job: &Job, config: &ProgramConfig) -> Option<(u8, u8)> {
+        let current = self.counted_votes(job, config);
+        let mut agent: Vec<u8> = current.clone().map(|v| v.agent_percentage).collect();
+        let mut bond: Vec<u8> = current.map(|v| v.bond_agent_percentage).collect();
+        if agent.is_empty() || agent.len() < job.arbitration_threshold(config) as usize {
+            return None;
+        }
+        agent.sort_unstable();
+        bond.sort_unstable();
+        let mid = (agent.len() - 1) / 2;
+        Some((agent[mid], bond[mid]))
+    }
+}
+
+/// One arbitrator's proposed split.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ArbitratorVote {
+    pub arbitrator: Pubkey,
+    /// Share of the disputed escrow to the agent (0-100)
+    pub agent_percentage: u8,
+    /// Share of the agent's bond returned to the agent (0-100)
+    pub bond_agent_percentage: u8,
+}
+
+impl ArbitratorVote {
+    /// arbitrator (32) + agent_percentage (1) + bond_agent_percentage (1) = 34
+    pub const LEN: usize = 32 + 1 + 1;
+}
+
+// ============================================================================
+// EVIDENCE - One party's supporting material for a dispute
+// ============================================================================
+
+#[account]
+pub struct Evidence {
+    /// Disputed job
+    pub job: Pubkey,
+    /// Requester or agent who submitted the evidence
+    pub party: Pubkey,
+    /// Dispute deadline of the dispute the items belong to; a record left
+    /// over from an earlier dispute is reset on the next submission
+    pub dispute_deadline: i64,
+    /// Submitted items, oldest first (max MAX_EVIDENCE_ITEMS)
+    pub items: Vec<EvidenceItem>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Evidence {
+    /// job (32) + party (32) + dispute_deadline (8)
+    /// + items (4 + 172 * MAX_EVIDENCE_ITEMS = 864) + bump (1) = 937
+    pub const LEN: usize = 32 + 32 + 8 + (4 + EvidenceItem::LEN * MAX_EVIDENCE_ITEMS) + 1;
+}
+
+/// A content hash and where the content can be fetched.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct EvidenceItem {
+    /// SHA-256 of the evidence content
+    pub content_hash: [u8; 32],
+    /// Where the content is hosted (max MAX_EVIDENCE_URI_LEN)
+    pub uri: String,
+    /// Unix timestamp of submission
+    pub submitted_at: i64,
+}
+
+impl EvidenceItem {
+    /// content_hash (32) + uri (4 + MAX_EVIDENCE_URI_LEN = 132)
+    /// + submitted_at (8) = 172
+    pub const LEN: usize = 32 + (4 + MAX_EVIDENCE_URI_LEN) + 8;
+}
+
+// ============================================================================
+// DELIVERABLES - Content-addressed record of submitted work
+// ============================================================================
+
+/// A submitted artifact: the hash of its content, where it can be fetched
+/// and what kind of content it is. Requesters and arbitrators verify a
+/// delivery by hashing the content behind `uri` and comparing.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq, Debug)]
+pub struct Deliverable {
+    /// SHA-256 of the artifact content
+    pub content_hash: [u8; 32],
+    /// Where the content is hosted (max MAX_DELIVERABLE_URI_LEN)
+    pub uri: String,
+    /// MIME type or similar tag (max MAX_CONTENT_TYPE_LEN)
+    pub content_type: String,
+}
+
+impl Deliverable {
+    /// content_hash (32) + uri (4 + MAX_DELIVERABLE_URI_LEN = 132)
+    /// + content_type (4 + MAX_CONTENT_TYPE_LEN = 36) = 200
+    pub const LEN: usize = 32 + (4 + MAX_DELIVERABLE_URI_LEN) + (4 + MAX_CONTENT_TYPE_LEN);
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.content_hash != [0u8; 32],
+            EscrowError::MissingContentHash
+        );
+        require!(
+            self.uri.len() <= MAX_DELIVERABLE_URI_LEN
+                && self.content_type.len() <= MAX_CONTENT_TYPE_LEN,
+            EscrowError::DeliverableTooLong
+        );
+        Ok(())
+    }
+}
+
+/// Extra artifacts of a job's latest submission, for work that does not fit
+/// a single deliverable. Created by the agent on the first submission that
+/// lists artifacts and overwritten by each later one.
+#[account]
+pub struct DeliverableArtifacts {
+    /// Job the artifacts were delivered for
+    pub job: Pubkey,
+    /// Agent who submitted them (and paid the rent)
+    pub agent: Pubkey,
+    /// Milestone the submission was for
+    pub milestone: u8,
+    /// Unix timestamp of the submission
+    pub submitted_at: i64,
+    /// Submitted artifacts (max MAX_ARTIFACTS)
+    pub artifacts: Vec<Deliverable>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl DeliverableArtifacts {
+    /// job (32) + agent (32) + milestone (1) + submitted_at (8)
+    /// + artifacts (4 + 200 * MAX_ARTIFACTS = 1604) + bump (1) = 1678
+    pub const LEN: usize = 32 + 32 + 1 + 8 + (4 + Deliverable::LEN * MAX_ARTIFACTS) + 1;
+}
+
+// ============================================================================
+// AGENT PROFILE - Aggregated on-chain reputation of an agent
+// ============================================================================
+
+#[account]
+pub struct AgentProfile {
+    /// Agent this profile belongs to
+    pub agent: Pubkey,
+    /// Jobs approved through to completion
+    pub jobs_completed: u32,
+    /// Sum of final ratings (1-5) on completed jobs
+    pub rating_sum: u64,
+    /// Completed jobs that were rated (auto-approved jobs carry no rating)
+    pub ratings_count: u32,
+    /// Disputes resolved with at least half of the escrow to the agent
+    pub disputes_won: u32,
+    /// Disputes resolved with less than half of the escrow to the agent
+    pub disputes_lost: u32,
+    /// Accepted jobs the agent walked away from via abandon_job
+    pub jobs_abandoned: u32,
+    /// Net amount paid to the agent, per mint (native SOL = default pubkey)
+    pub volume: Vec<MintVolume>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl AgentProfile {
+    /// agent (32) + jobs_completed (4) + rating_sum (8) + ratings_count (4)
+    /// + disputes_won (4) + disputes_lost (4) + jobs_abandoned (4)
+    /// + volume (4 + 40 * MAX_PROFILE_MINTS = 324) + bump (1) = 385
+    pub const LEN: usize =
+        32 + 4 + 8 + 4 + 4 + 4 + 4 + (4 + MintVolume::LEN * MAX_PROFILE_MINTS) + 1;
+
+    /// Add a payout to the agent's volume for `mint`. Once MAX_PROFILE_MINTS
+    /// mints are tracked, payouts in further mints are not recorded so that a
+    /// full profile never blocks a payment.
+    pub fn record_payout(&mut self, mint: Pubkey, amount: u64) -> Result<()> {
+        if let Some(entry) = self.volume.iter_mut().find(|v| v.mint == mint) {
+            entry.amount = entry.amount.checked_add(amount).ok_or(EscrowError::Overflow)?;
+        } else if self.volume.len() < MAX_PROFILE_MINTS {
+            self.volume.push(MintVolume { mint, amount });
+        }
+        Ok(())
+    }
+
+    /// Record a job paid through to completion with its final rating, if
+    /// the requester gave one.
+    pub fn record_completion(&mut self, rating: Option<u8>) -> Result<()> {
+        self.jobs_completed = self.jobs_completed.checked_add(1).ok_or(EscrowError::Overflow)?;
+        if let Some(rating) = rating {
+            self.rating_sum = self
+                .rating_sum
+                .checked_add(rating as u64)
+                .ok_or(EscrowError::Overflow)?;
+            self.ratings_count = self.ratings_count.checked_add(1).ok_or(EscrowError::Overflow)?;
+        }
+        Ok(())
+    }
+
+    /// Record a resolved dispute. The agent wins if awarded at least half.
+    pub fn record_dispute(&mut self, agent_percentage: u8) -> Result<()> {
+        if agent_percentage >= 50 {
+            self.disputes_won = self.disputes_won.checked_add(1).ok_or(EscrowError::Overflow)?;
+        } else {
+            self.disputes_lost = self.disputes_lost.checked_add(1).ok_or(EscrowError::Overflow)?;
+        }
+        Ok(())
+    }
+
+    /// Record a job the agent abandoned after accepting it.
+    pub fn record_abandonment(&mut self) -> Result<()> {
+        self.jobs_abandoned = self.jobs_abandoned.checked_add(1).ok_or(EscrowError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// Cumulative payouts to an agent in one mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MintVolume {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl MintVolume {
+    /// mint (32) + amount (8) = 40
+    pub const LEN: usize = 32 + 8;
+}
+
+// ============================================================================
+// REQUESTER PROFILE - Aggregated ratings agents gave a requester
+// ============================================================================
+
+#[account]
+pub struct RequesterProfile {
+    /// Requester this profile belongs to
+    pub requester: Pubkey,
+    /// Number of ratings received from agents
+    pub ratings_count: u32,
+    /// Sum of ratings (1-5) received from agents
+    pub rating_sum: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RequesterProfile {
+    /// requester (32) + ratings_count (4) + rating_sum (8) + bump (1) = 45
+    pub const LEN: usize = 32 + 4 + 8 + 1;
+
+    /// Add one agent rating to the aggregate.
+    pub fn record_rating(&mut self, rating: u8) -> Result<()> {
+        self.ratings_count = self.ratings_count.checked_add(1).ok_or(EscrowError::Overflow)?;
+        self.rating_sum = self
+            .rating_sum
+            .checked_add(rating as u64)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// SOL VAULT / SOL TREASURY - Program-owned lamport holders for native jobs
+// ============================================================================
+
+/// Holds the escrowed lamports of a native SOL job. Carries no data; the
+/// escrowed amount is tracked on the Job and sits on top of the rent reserve.
+#[account]
+pub struct SolVault {}
+
+impl SolVault {
+    pub const LEN: usize = 0;
+}
+
+/// Accumulates protocol fees charged on native SOL payouts.
+#[account]
+pub struct SolTreasury {}
+
+impl SolTreasury {
+    pub const LEN: usize = 0;
+}
+
+// ============================================================================
+// JOB ARCHIVE - Compact record kept after a terminal job is closed
+// ============================================================================
+
+#[account]
+pub struct JobArchive {
+    /// Address of the closed Job account
+    pub job: Pubkey,
+    /// Original job identifier (max 36 chars)
+    pub job_id: String,
+    /// Requester who posted and funded the job
+    pub requester: Pubkey,
+    /// Agent assigned to the job (Pubkey::default() if never assigned)
+    pub agent: Pubkey,
+    /// Token mint the job was escrowed in
+    pub mint: Pubkey,
+    /// Escrowed amount in token base units
+    pub amount: u64,
+    /// Terminal status the job reached
+    pub status: JobStatus,
+    /// Rating given by requester (1-5, 0 = unrated)
+    pub rating: u8,
+    /// Rating given by agent to the requester (1-5, 0 = unrated)
+    pub requester_rating: u8,
+    /// Unix timestamp when job was created
+    pub created_at: i64,
+    /// Unix timestamp when job was closed
+    pub closed_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl JobArchive {
+    /// job (32) + job_id (4 + 36) + requester (32) + agent (32) + mint (32)
+    /// + amount (8) + status (1) + rating (1) + requester_rating (1)
+    /// + created_at (8) + closed_at (8) + bump (1) = 196
+    pub const LEN: usize = 32
+        + (4 + MAX_JOB_ID_LEN)
+        + 32  // requester
+        + 32  // agent
+        + 32  // mint
+        + 8   // amount
+        + 1   // status
+        + 1   // rating
+        + 1   // requester_rating
+        + 8   // created_at
+        + 8   // closed_at
+        + 1;  // bump
+}
+
+// ============================================================================
+// ARBITRATION FEE - Paid to the arbitrator out of a disputed escrow
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ArbitrationFee {
+    /// Fixed amount or basis points of the disputed escrow
+    pub kind: ArbitrationFeeKind,
+    /// Token base units (Fixed) or basis points (Bps, max MAX_FEE_BPS)
+    pub value: u64,
+    /// Which party's award the fee is taken from
+    pub bearer: FeeBearer,
+}
+
+impl ArbitrationFee {
+    /// kind (1) + value (8) + bearer (1) = 10
+    pub const LEN: usize = 1 + 8 + 1;
+
+    pub fn validate(&self) -> Result<()> {
+        if self.kind == ArbitrationFeeKind::Bps {
+            require!(self.value <= MAX_FEE_BPS as u64, EscrowError::FeeTooHigh);
+        }
+        Ok(())
+    }
+
+    /// Fee owed on a dispute over `disputed` tokens, capped at `disputed`.
+    pub fn amount(&self, disputed: u64) -> Result<u64> {
+        let fee = match self.kind {
+            ArbitrationFeeKind::Fixed => self.value,
+            ArbitrationFeeKind::Bps => (disputed as u128)
+                .checked_mul(self.value as u128)
+                .ok_or(EscrowError::Overflow)?
+                .checked_div(BPS_DENOMINATOR as u128)
+                .ok_or(EscrowError::Overflow)? as u64,
+        };
+        Ok(fee.min(disputed))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum ArbitrationFeeKind {
+    #[default]
+    Fixed,
+    Bps,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum FeeBearer {
+    /// Each party pays in proportion to the share of the escrow they lost
+    #[default]
+    LoserPays,
+    /// Agent and requester pay half each
+    Split,
+}
+
+// ============================================================================
+// DISPUTE FALLBACK - Applied when no arbitrator resolves a dispute in time
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DisputeFallback {
+    /// Days the arbitrators have to resolve a dispute once it is opened
+    pub days: u8,
+    /// Agent's share (0-100) of the disputed escrow once the window lapses
+    pub agent_percentage: u8,
+    /// Agent's share (0-100) of their bond once the window lapses
+    pub bond_agent_percentage: u8,
+}
+
+impl Default for DisputeFallback {
+    fn default() -> Self {
+        Self {
+            days: DEFAULT_DISPUTE_DAYS,
+            agent_percentage: 50,
+            bond_agent_percentage: 50,
+        }
+    }
+}
+
+impl DisputeFallback {
+    /// days (1) + agent_percentage (1) + bond_agent_percentage (1) = 3
+    pub const LEN: usize = 1 + 1 + 1;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.days > 0, EscrowError::InvalidDisputeWindow);
+        require!(
+            self.agent_percentage <= 100 && self.bond_agent_percentage <= 100,
+            EscrowError::InvalidPercentage
+        );
+        Ok(())
+    }
+
+    /// Dispute window length in seconds.
+    pub fn period(&self) -> i64 {
+        self.days as i64 * 86400
+    }
+}
+
+// ============================================================================
+// ESCROW SPLIT - Division of the escrow between the two parties
+// ============================================================================
+
+/// Amounts paid out when a dispute or settlement is decided by a split,
+/// computed by Job::split_remaining or Job::split_disputed.
+pub struct EscrowSplit {
+    /// Whether the split settles the whole job; otherwise only the current
+    /// milestone was split and the job moves on to the next one
+    pub ends_job: bool,
+    /// Share of the escrow awarded to the agent before fees
+    pub agent_percentage: u8,
+    /// Paid to the agent, net of arbitration and protocol fees
+    pub agent_payout: u64,
+    /// Refunded to the requester, net of arbitration fee
+    pub requester_amount: u64,
+    /// Protocol fee routed to the treasury
+    pub fee: u64,
+    /// Arbitration fee owed to the arbitrator (0 without arbitration)
+    pub arbitration_fee: u64,
+    /// Bond returned to the agent
+    pub bond_agent_amount: u64,
+    /// Bond forfeited to the requester
+    pub bond_requester_amount: u64,
+}
+
+// ============================================================================
+// ASSET KIND ENUM
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AssetKind {
+    /// SPL Token or Token-2022 mint held in the escrow token PDA
+    SplToken,
+    /// Native lamports held in the SOL vault PDA
+    NativeSol,
 }
 
 // ============================================================================
@@ -128,6 +1550,17 @@ pub enum JobStatus {
     Cancelled,
     /// Work rejected, under dispute
     Disputed,
-    /// Arbitrator resolved the dispute
+    /// Escrow split by the arbitrators, the dispute fallback or a mutual
+    /// settlement
     Resolved,
 }
+
+impl JobStatus {
+    /// Whether the job has reached a final state and its escrow is settled.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Completed | JobStatus::Cancelled | JobStatus::Resolved
+        )
+    }
+}
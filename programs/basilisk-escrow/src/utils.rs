@@ -0,0 +1,272 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+    onchain::invoke_transfer_checked,
+    state::Account as SplAccount,
+};
+use anchor_spl::token_interface::{
+    self, harvest_withheld_tokens_to_mint, CloseAccount, HarvestWithheldTokensToMint, Mint,
+    TokenAccount, TokenInterface,
+};
+use crate::errors::EscrowError;
+use crate::state::{AssetKind, DeliverableArtifacts, DisputeVote};
+
+// ============================================================================
+// ESCROW - Uniform payout interface over token and native SOL escrows
+// ============================================================================
+
+/// A job's escrowed funds and the accounts needed to move them out.
+///
+/// Token jobs hold funds in the escrow token PDA and pay out with
+/// `transfer_checked`; native jobs hold lamports in the program-owned SOL
+/// vault PDA and pay out by moving lamports directly.
+pub enum Escrow<'a, 'info> {
+    Token {
+        token_program: &'a Interface<'info, TokenInterface>,
+        escrow_token: &'a InterfaceAccount<'info, TokenAccount>,
+        mint: &'a InterfaceAccount<'info, Mint>,
+        authority: AccountInfo<'info>,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    },
+    Native {
+        vault: AccountInfo<'info>,
+    },
+}
+
+impl<'a, 'info> Escrow<'a, 'info> {
+    /// Select the escrow matching `asset`, failing if the accounts that kind
+    /// of job needs were not supplied.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        asset: AssetKind,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+        escrow_token: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        mint: &'a Option<InterfaceAccount<'info, Mint>>,
+        authority: AccountInfo<'info>,
+        sol_vault: Option<AccountInfo<'info>>,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> Result<Self> {
+        match asset {
+            AssetKind::SplToken => Ok(Escrow::Token {
+                token_program: token_program.as_ref().ok_or(EscrowError::MissingAccount)?,
+                escrow_token: escrow_token.as_ref().ok_or(EscrowError::MissingAccount)?,
+                mint: mint.as_ref().ok_or(EscrowError::MissingAccount)?,
+                authority,
+                remaining_accounts,
+            }),
+            AssetKind::NativeSol => Ok(Escrow::Native {
+                vault: sol_vault.ok_or(EscrowError::MissingAccount)?,
+            }),
+        }
+    }
+
+    /// Pick the destination matching this escrow: a token account for token
+    /// jobs, a wallet for native jobs.
+    pub fn destination(
+        &self,
+        token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        wallet: Option<AccountInfo<'info>>,
+    ) -> Result<AccountInfo<'info>> {
+        let destination = match self {
+            Escrow::Token { .. } => token_account.map(|account| account.to_account_info()),
+            Escrow::Native { .. } => wallet,
+        };
+        destination.ok_or_else(|| error!(EscrowError::MissingAccount))
+    }
+
+    /// Validate a destination passed without a typed account (through
+    /// remaining accounts): a token account of the job's mint held by
+    /// `owner` for token jobs, `owner`'s wallet itself for native jobs.
+    pub fn payee(&self, account: &AccountInfo<'info>, owner: &Pubkey) -> Result<AccountInfo<'info>> {
+        match self {
+            Escrow::Token {
+                token_program,
+                mint,
+                ..
+            } => {
+                require_keys_eq!(*account.owner, token_program.key(), EscrowError::InvalidTokenOwner);
+                let token_account = {
+                    let data = account.try_borrow_data()?;
+                    TokenAccount::try_deserialize(&mut &data[..])?
+                };
+                require_keys_eq!(token_account.owner, *owner, EscrowError::InvalidTokenOwner);
+                require_keys_eq!(token_account.mint, mint.key(), EscrowError::InvalidMint);
+            }
+            Escrow::Native { .. } => {
+                require_keys_eq!(account.key(), *owner, EscrowError::InvalidRecipient);
+            }
+        }
+        Ok(account.clone())
+    }
+
+    /// Pay `amount` out of the escrow to `to`. `signer_seeds` are the escrow
+    /// authority seeds (unused for native vaults, which the program owns).
+    pub fn pay(
+        &self,
+        to: AccountInfo<'info>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        match self {
+            Escrow::Token {
+                token_program,
+                escrow_token,
+                mint,
+                authority,
+                remaining_accounts,
+            } => transfer_tokens(
+                token_program,
+                escrow_token.to_account_info(),
+                mint,
+                to,
+                authority.clone(),
+                remaining_accounts,
+                amount,
+                signer_seeds,
+            ),
+            Escrow::Native { vault } => transfer_lamports(vault, &to, amount),
+        }
+    }
+}
+
+// ============================================================================
+// LAMPORT HELPERS
+// ============================================================================
+
+/// Move lamports out of an account owned by this program.
+pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let from_balance = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(EscrowError::Overflow)?;
+    let to_balance = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(EscrowError::Overflow)?;
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    Ok(())
+}
+
+/// Close an account owned by this program, returning its rent to
+/// `destination`. For PDAs that may not exist and so cannot be declared as
+/// typed accounts with `close = ...`.
+pub fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    transfer_lamports(account, destination, account.lamports())?;
+    account.assign(&anchor_lang::system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+/// Close a job's DeliverableArtifacts PDA, if it exists, returning its rent
+/// to the agent who paid for it. `agent` must then be that agent's wallet.
+pub fn close_artifacts(artifacts: &AccountInfo, agent: Option<&AccountInfo>) -> Result<()> {
+    if artifacts.owner != &crate::ID {
+        return Ok(());
+    }
+    let owner = {
+        let data = artifacts.try_borrow_data()?;
+        DeliverableArtifacts::try_deserialize(&mut &data[..])?.agent
+    };
+    let agent = agent.ok_or(EscrowError::MissingAccount)?;
+    require_keys_eq!(agent.key(), owner, EscrowError::InvalidRecipient);
+    close_program_account(artifacts, agent)
+}
+
+/// Close a job's DisputeVote PDA, if it exists, returning its rent to the
+/// arbitrator who cast the first vote. `payer` must then be that
+/// arbitrator's wallet.
+pub fn close_dispute_vote(dispute_vote: &AccountInfo, payer: Option<&AccountInfo>) -> Result<()> {
+    if dispute_vote.owner != &crate::ID {
+        return Ok(());
+    }
+    let owner = {
+        let data = dispute_vote.try_borrow_data()?;
+        DisputeVote::try_deserialize(&mut &data[..])?.payer
+    };
+    let payer = payer.ok_or(EscrowError::MissingAccount)?;
+    require_keys_eq!(payer.key(), owner, EscrowError::InvalidRecipient);
+    close_program_account(dispute_vote, payer)
+}
+
+// ============================================================================
+// TOKEN HELPERS - Shared by every instruction that moves escrowed tokens.
+// Works for both the legacy SPL Token program and Token-2022.
+// ============================================================================
+
+/// Move `amount` tokens with `transfer_checked`.
+///
+/// For Token-2022 mints with a transfer hook, the extra accounts required by
+/// the hook program are resolved from `remaining_accounts`, so callers must
+/// forward `ctx.remaining_accounts` unchanged.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_tokens<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint.to_account_info(),
+        to,
+        authority,
+        remaining_accounts,
+        amount,
+        mint.decimals,
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Close a PDA-owned token account, returning its rent to `destination`.
+///
+/// Token-2022 refuses to close accounts that still hold withheld transfer
+/// fees, so any withheld amount is harvested to the mint first. The account
+/// balance itself must already be zero.
+pub fn close_token_account<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if withheld_fee_amount(&account.to_account_info())? > 0 {
+        let cpi_ctx = CpiContext::new(
+            token_program.to_account_info(),
+            HarvestWithheldTokensToMint {
+                token_program_id: token_program.to_account_info(),
+                mint: mint.to_account_info(),
+            },
+        );
+        harvest_withheld_tokens_to_mint(cpi_ctx, vec![account.to_account_info()])?;
+    }
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: account.to_account_info(),
+            destination,
+            authority,
+        },
+        signer_seeds,
+    );
+    token_interface::close_account(cpi_ctx)
+}
+
+/// Transfer fees withheld on a Token-2022 account (0 for legacy accounts or
+/// mints without the transfer-fee extension).
+fn withheld_fee_amount(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<SplAccount>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferFeeAmount>()
+        .map(|fee| u64::from(fee.withheld_amount))
+        .unwrap_or(0))
+}
@@ -111,7 +111,7 @@ echo "╚═══════════════════════
 echo ""
 echo "NEXT STEPS:"
 echo "  1. Run: anchor test --provider.cluster $CLUSTER"
-echo "  2. Initialize config: Call 'initialize' with your arbitrator pubkey"
+echo "  2. Initialize config: Call 'initialize' with your arbitrator pubkeys and vote threshold"
 echo "  3. Verify on Explorer: https://explorer.solana.com/address/$PROGRAM_ID"
 if [ "$CLUSTER" = "devnet" ]; then
 echo "  4. When ready: ./scripts/deploy.sh mainnet"
@@ -5,15 +5,23 @@ import {
   PublicKey,
   SystemProgram,
   SYSVAR_RENT_PUBKEY,
+  Transaction,
+  sendAndConfirmTransaction,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
   createMint,
   createAccount,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getMintLen,
   mintTo,
   getAccount,
 } from "@solana/spl-token";
 import { assert, expect } from "chai";
+import * as pda from "../app/pda";
 
 // Type will be generated by anchor build
 // import { BasiliskEscrow } from "../target/types/basilisk_escrow";
@@ -35,6 +43,7 @@ describe("basilisk-escrow", () => {
   let requesterToken: PublicKey;
   let agentToken: PublicKey;
   let adminToken: PublicKey;
+  let treasuryToken: PublicKey;
 
   const JOB_ID = "test-job-001";
   const JOB_AMOUNT = 10_000_000; // 10 tokens (6 decimals)
@@ -42,32 +51,129 @@ describe("basilisk-escrow", () => {
   const DEADLINE_DAYS = 7;
 
   // ── PDA derivation helpers ────────────────────────────────────────────
+  // Job PDAs default to `requester`, who creates every job in this suite.
   function findConfigPDA(): [PublicKey, number] {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("config")],
-      program.programId
-    );
+    return pda.findConfigPDA(program.programId);
   }
 
-  function findJobPDA(jobId: string): [PublicKey, number] {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("job"), Buffer.from(jobId)],
-      program.programId
-    );
+  function findJobPDA(jobId: string, owner = requester.publicKey): [PublicKey, number] {
+    return pda.findJobPDA(program.programId, owner, jobId);
   }
 
-  function findEscrowAuthorityPDA(jobId: string): [PublicKey, number] {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("escrow"), Buffer.from(jobId)],
-      program.programId
-    );
+  function findEscrowAuthorityPDA(jobId: string, owner = requester.publicKey): [PublicKey, number] {
+    return pda.findEscrowAuthorityPDA(program.programId, owner, jobId);
   }
 
-  function findEscrowTokenPDA(jobId: string): [PublicKey, number] {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("escrow_token"), Buffer.from(jobId)],
-      program.programId
-    );
+  function findEscrowTokenPDA(jobId: string, owner = requester.publicKey): [PublicKey, number] {
+    return pda.findEscrowTokenPDA(program.programId, owner, jobId);
+  }
+
+  function findSolVaultPDA(jobId: string, owner = requester.publicKey): [PublicKey, number] {
+    return pda.findSolVaultPDA(program.programId, owner, jobId);
+  }
+
+  function findBondTokenPDA(jobId: string, owner = requester.publicKey): [PublicKey, number] {
+    return pda.findBondTokenPDA(program.programId, owner, jobId);
+  }
+
+  function findBondVaultPDA(jobId: string, owner = requester.publicKey): [PublicKey, number] {
+    return pda.findBondVaultPDA(program.programId, owner, jobId);
+  }
+
+  function findAgentProfilePDA(agentKey: PublicKey): [PublicKey, number] {
+    return pda.findAgentProfilePDA(program.programId, agentKey);
+  }
+
+  function findRequesterProfilePDA(owner = requester.publicKey): [PublicKey, number] {
+    return pda.findRequesterProfilePDA(program.programId, owner);
+  }
+
+  function findDisputeVotePDA(job: PublicKey): [PublicKey, number] {
+    return pda.findDisputeVotePDA(program.programId, job);
+  }
+
+  function findEvidencePDA(job: PublicKey, party: PublicKey): [PublicKey, number] {
+    return pda.findEvidencePDA(program.programId, job, party);
+  }
+
+  function findArtifactsPDA(job: PublicKey): [PublicKey, number] {
+    return pda.findArtifactsPDA(program.programId, job);
+  }
+
+  function findArbitratorRegistrationPDA(key: PublicKey): [PublicKey, number] {
+    return pda.findArbitratorRegistrationPDA(program.programId, key);
+  }
+
+  function findApplicationPDA(job: PublicKey, applicant: PublicKey): [PublicKey, number] {
+    return pda.findApplicationPDA(program.programId, job, applicant);
+  }
+
+  function findTreasuryPDA(mint: PublicKey): [PublicKey, number] {
+    return pda.findTreasuryPDA(program.programId, mint);
+  }
+
+  function findSolTreasuryPDA(): [PublicKey, number] {
+    return pda.findSolTreasuryPDA(program.programId);
+  }
+
+  function findArchivePDA(job: PublicKey, createdAt: anchor.BN): [PublicKey, number] {
+    return pda.findArchivePDA(program.programId, job, createdAt.toNumber());
+  }
+
+  // Run `call` and return the `name` event it emitted, picked up by an
+  // event listener (null if none arrived).
+  async function captureEvent(name: string, call: () => Promise<unknown>): Promise<any> {
+    let event: any = null;
+    const listener = program.addEventListener(name, (e) => {
+      event = e;
+    });
+    try {
+      await call();
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+    } finally {
+      await program.removeEventListener(listener);
+    }
+    return event;
+  }
+
+  // Agent rates the requester of a settled job, which lets it be closed
+  // before the rating window ends.
+  function rateSettledJob(job: PublicKey, signer = agent, rating = 4) {
+    return program.methods
+      .rateRequester(rating)
+      .accounts({
+        job,
+        requesterProfile: findRequesterProfilePDA()[0],
+        agent: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  // Optional job terms; defaults to a plain public, unbonded job.
+  function terms(overrides: Record<string, any> = {}) {
+    return {
+      milestones: [],
+      allowedAgents: [],
+      bondAmount: new anchor.BN(0),
+      bondBps: 0,
+      arbitrator: null,
+      arbitrationFee: null,
+      reviewDays: null,
+      maxRevisions: 0,
+      ...overrides,
+    };
+  }
+
+  // Content-hashed deliverable; the hash is arbitrary but non-zero.
+  function deliverable(uri: string, overrides: Record<string, any> = {}) {
+    return {
+      contentHash: Array(32).fill(7),
+      uri,
+      contentType: "text/html",
+      ...overrides,
+    };
   }
 
   // ── Setup ─────────────────────────────────────────────────────────────
@@ -90,6 +196,7 @@ describe("basilisk-escrow", () => {
       null,
       6 // 6 decimals
     );
+    [treasuryToken] = findTreasuryPDA(mint);
 
     // Create token accounts
     requesterToken = await createAccount(
@@ -121,11 +228,11 @@ describe("basilisk-escrow", () => {
   // ====================================================================
 
   describe("Initialize", () => {
-    it("initializes program config with admin and arbitrator", async () => {
+    it("initializes program config with admin and arbitrator panel", async () => {
       const [configPDA] = findConfigPDA();
 
       await program.methods
-        .initialize(arbitrator.publicKey)
+        .initialize([arbitrator.publicKey], 1)
         .accounts({
           config: configPDA,
           admin: admin.publicKey,
@@ -136,7 +243,9 @@ describe("basilisk-escrow", () => {
 
       const config = await program.account.programConfig.fetch(configPDA);
       assert.ok(config.admin.equals(admin.publicKey));
-      assert.ok(config.arbitrator.equals(arbitrator.publicKey));
+      assert.equal(config.arbitrators.length, 1);
+      assert.ok(config.arbitrators[0].equals(arbitrator.publicKey));
+      assert.equal(config.threshold, 1);
     });
 
     it("fails to initialize twice", async () => {
@@ -144,7 +253,7 @@ describe("basilisk-escrow", () => {
 
       try {
         await program.methods
-          .initialize(arbitrator.publicKey)
+          .initialize([arbitrator.publicKey], 1)
           .accounts({
             config: configPDA,
             admin: admin.publicKey,
@@ -168,21 +277,30 @@ describe("basilisk-escrow", () => {
       const [configPDA] = findConfigPDA();
       const newArbitrator = Keypair.generate();
 
-      await program.methods
-        .updateConfig(newArbitrator.publicKey, null)
-        .accounts({
-          config: configPDA,
-          admin: admin.publicKey,
-        })
-        .signers([admin])
-        .rpc();
+      const event = await captureEvent("configUpdated", () =>
+        program.methods
+          .updateConfig([newArbitrator.publicKey], null, null, null, null, null, null)
+          .accounts({
+            config: configPDA,
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc()
+      );
 
       const config = await program.account.programConfig.fetch(configPDA);
-      assert.ok(config.arbitrator.equals(newArbitrator.publicKey));
+      assert.ok(config.arbitrators[0].equals(newArbitrator.publicKey));
+
+      assert.ok(event, "ConfigUpdated event not emitted");
+      assert.ok(event.admin.equals(admin.publicKey));
+      assert.equal(event.arbitrators.length, 1);
+      assert.ok(event.arbitrators[0].equals(newArbitrator.publicKey));
+      assert.equal(event.threshold, config.threshold);
+      assert.equal(event.feeBps, config.feeBps);
 
       // Restore original arbitrator for remaining tests
       await program.methods
-        .updateConfig(arbitrator.publicKey, null)
+        .updateConfig([arbitrator.publicKey], null, null, null, null, null, null)
         .accounts({
           config: configPDA,
           admin: admin.publicKey,
@@ -191,12 +309,25 @@ describe("basilisk-escrow", () => {
         .rpc();
     });
 
+    it("rejects a threshold larger than the panel", async () => {
+      try {
+        await program.methods
+          .updateConfig(null, null, null, 2, null, null, null)
+          .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidThreshold");
+      }
+    });
+
     it("non-admin cannot update config", async () => {
       const [configPDA] = findConfigPDA();
 
       try {
         await program.methods
-          .updateConfig(unauthorizedUser.publicKey, null)
+          .updateConfig([unauthorizedUser.publicKey], null, null, null, null, null, null)
           .accounts({
             config: configPDA,
             admin: unauthorizedUser.publicKey,
@@ -208,6 +339,23 @@ describe("basilisk-escrow", () => {
         expect(err.toString()).to.contain("Unauthorized");
       }
     });
+
+    it("refuses to migrate a config that already uses the current layout", async () => {
+      try {
+        await program.methods
+          .migrateConfig()
+          .accounts({
+            config: findConfigPDA()[0],
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([admin])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("ConfigAlreadyMigrated");
+      }
+    });
   });
 
   // ====================================================================
@@ -223,7 +371,7 @@ describe("basilisk-escrow", () => {
       const balanceBefore = (await getAccount(provider.connection, requesterToken)).amount;
 
       await program.methods
-        .createJob(JOB_ID, new anchor.BN(JOB_AMOUNT), JOB_DESCRIPTION, DEADLINE_DAYS)
+        .createJob(JOB_ID, new anchor.BN(JOB_AMOUNT), JOB_DESCRIPTION, DEADLINE_DAYS, terms())
         .accounts({
           job: jobPDA,
           escrowAuthority: escrowAuthority,
@@ -231,6 +379,8 @@ describe("basilisk-escrow", () => {
           requester: requester.publicKey,
           requesterToken: requesterToken,
           mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -257,6 +407,48 @@ describe("basilisk-escrow", () => {
       assert.equal(Number(balanceBefore) - Number(balanceAfter), JOB_AMOUNT);
     });
 
+    it("emits JobCreated event", async () => {
+      const jobId = "event-test";
+      const [jobPDA] = findJobPDA(jobId);
+      const [escrowAuthority] = findEscrowAuthorityPDA(jobId);
+      const [escrowToken] = findEscrowTokenPDA(jobId);
+
+      let event: any = null;
+      const listener = program.addEventListener("jobCreated", (e) => {
+        event = e;
+      });
+
+      await program.methods
+        .createJob(jobId, new anchor.BN(1_000_000), "Event test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: escrowAuthority,
+          escrowToken: escrowToken,
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      assert.ok(event, "JobCreated event not emitted");
+      assert.equal(event.jobId, jobId);
+      assert.ok(event.job.equals(jobPDA));
+      assert.ok(event.requester.equals(requester.publicKey));
+      assert.ok(event.mint.equals(mint));
+      assert.equal(event.amount.toNumber(), 1_000_000);
+      const job = await program.account.job.fetch(jobPDA);
+      assert.equal(event.deadline.toNumber(), job.deadline.toNumber());
+    });
+
     it("fails with zero amount", async () => {
       const jobId = "zero-amount-job";
       const [jobPDA] = findJobPDA(jobId);
@@ -265,7 +457,7 @@ describe("basilisk-escrow", () => {
 
       try {
         await program.methods
-          .createJob(jobId, new anchor.BN(0), "Test", 7)
+          .createJob(jobId, new anchor.BN(0), "Test", 7, terms())
           .accounts({
             job: jobPDA,
             escrowAuthority: escrowAuthority,
@@ -273,6 +465,8 @@ describe("basilisk-escrow", () => {
             requester: requester.publicKey,
             requesterToken: requesterToken,
             mint: mint,
+            config: findConfigPDA()[0],
+            treasuryToken: treasuryToken,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
             rent: SYSVAR_RENT_PUBKEY,
@@ -293,7 +487,7 @@ describe("basilisk-escrow", () => {
 
       try {
         await program.methods
-          .createJob(longJobId, new anchor.BN(1000), "Test", 7)
+          .createJob(longJobId, new anchor.BN(1000), "Test", 7, terms())
           .accounts({
             job: jobPDA,
             escrowAuthority: escrowAuthority,
@@ -301,6 +495,8 @@ describe("basilisk-escrow", () => {
             requester: requester.publicKey,
             requesterToken: requesterToken,
             mint: mint,
+            config: findConfigPDA()[0],
+            treasuryToken: treasuryToken,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
             rent: SYSVAR_RENT_PUBKEY,
@@ -312,6 +508,122 @@ describe("basilisk-escrow", () => {
         expect(err.toString()).to.contain("JobIdTooLong");
       }
     });
+
+    it("accepts a canonical 36-char UUID job ID", async () => {
+      const uuidJobId = "3f2b8c4e-9a1d-4e7f-b6c2-5d8e0a1f7b93";
+      assert.equal(uuidJobId.length, 36);
+      const [jobPDA] = findJobPDA(uuidJobId);
+
+      await program.methods
+        .createJob(uuidJobId, new anchor.BN(1_000_000), "UUID test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(uuidJobId)[0],
+          escrowToken: findEscrowTokenPDA(uuidJobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.equal(job.jobId, uuidJobId);
+      assert.deepEqual(Buffer.from(job.jobIdHash), pda.jobIdHash(uuidJobId));
+    });
+
+    it("runs a 36-char job ID through payout and close", async () => {
+      const uuidJobId = "3f2b8c4e-9a1d-4e7f-b6c2-5d8e0a1f7b93";
+      const [jobPDA] = findJobPDA(uuidJobId);
+      const [escrowAuthority] = findEscrowAuthorityPDA(uuidJobId);
+      const [escrowToken] = findEscrowTokenPDA(uuidJobId);
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      await program.methods
+        .approveAndPay(0, 5)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority,
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken,
+            agentToken: agentToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+      await rateSettledJob(jobPDA);
+
+      await program.methods
+        .closeJob()
+        .accounts({
+          job: jobPDA,
+          artifacts: findArtifactsPDA(jobPDA)[0],
+          escrowAuthority,
+          requester: requester.publicKey,
+          archive: null,
+          escrowToken,
+          requesterToken: requesterToken,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+
+      assert.isNull(await provider.connection.getAccountInfo(jobPDA));
+    });
+
+    it("SECURITY: job_id cannot be squatted by another requester", async () => {
+      const jobId = "squat-test";
+
+      function createSolJobAs(signer: Keypair, owner: PublicKey) {
+        return program.methods
+          .createSolJob(jobId, new anchor.BN(1_000_000), "Squat test", 7, terms())
+          .accounts({
+            job: findJobPDA(jobId, owner)[0],
+            escrowAuthority: findEscrowAuthorityPDA(jobId, owner)[0],
+            solVault: findSolVaultPDA(jobId, owner)[0],
+            config: findConfigPDA()[0],
+            solTreasury: findSolTreasuryPDA()[0],
+            requester: signer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([signer])
+          .rpc();
+      }
+
+      // An attacker cannot create a job at the requester's PDA...
+      try {
+        await createSolJobAs(unauthorizedUser, requester.publicKey);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("ConstraintSeeds");
+      }
+
+      // ...and using the same job_id in their own namespace does not block it
+      await createSolJobAs(unauthorizedUser, unauthorizedUser.publicKey);
+      await createSolJobAs(requester, requester.publicKey);
+
+      const job = await program.account.job.fetch(findJobPDA(jobId)[0]);
+      assert.ok(job.requester.equals(requester.publicKey));
+    });
   });
 
   // ====================================================================
@@ -322,18 +634,26 @@ describe("basilisk-escrow", () => {
     it("agent accepts open job", async () => {
       const [jobPDA] = findJobPDA(JOB_ID);
 
-      await program.methods
-        .acceptJob()
-        .accounts({
-          job: jobPDA,
-          agent: agent.publicKey,
-        })
-        .signers([agent])
-        .rpc();
+      const event = await captureEvent("jobAccepted", () =>
+        program.methods
+          .acceptJob()
+          .accounts({
+            job: jobPDA,
+            agent: agent.publicKey,
+          })
+          .signers([agent])
+          .rpc()
+      );
 
       const job = await program.account.job.fetch(jobPDA);
       assert.ok(job.agent.equals(agent.publicKey));
       assert.deepEqual(job.status, { inProgress: {} });
+
+      assert.ok(event, "JobAccepted event not emitted");
+      assert.ok(event.job.equals(jobPDA));
+      assert.equal(event.jobId, JOB_ID);
+      assert.ok(event.agent.equals(agent.publicKey));
+      assert.equal(event.bond.toNumber(), 0);
     });
 
     it("fails to accept already-taken job", async () => {
@@ -353,6 +673,83 @@ describe("basilisk-escrow", () => {
         // Should fail — job already in progress
       }
     });
+
+    it("only invited agents can accept a private job", async () => {
+      const jobId = "private-job";
+      const [jobPDA] = findJobPDA(jobId);
+
+      await program.methods
+        .createJob(jobId, new anchor.BN(1_000_000), "Private test", 7, terms({ allowedAgents: [agent.publicKey] }))
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      try {
+        await program.methods
+          .acceptJob()
+          .accounts({ job: jobPDA, agent: unauthorizedUser.publicKey })
+          .signers([unauthorizedUser])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("AgentNotInvited");
+      }
+
+      await program.methods
+        .acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent])
+        .rpc();
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.ok(job.agent.equals(agent.publicKey));
+    });
+
+    it("SECURITY: requester cannot accept their own job", async () => {
+      const jobId = "self-accept-test";
+      const [jobPDA] = findJobPDA(jobId);
+
+      await program.methods
+        .createJob(jobId, new anchor.BN(1_000_000), "Self-accept test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      try {
+        await program.methods
+          .acceptJob()
+          .accounts({ job: jobPDA, agent: requester.publicKey })
+          .signers([requester])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidAgent");
+      }
+    });
   });
 
   // ====================================================================
@@ -363,18 +760,31 @@ describe("basilisk-escrow", () => {
     it("assigned agent submits deliverable", async () => {
       const [jobPDA] = findJobPDA(JOB_ID);
 
-      await program.methods
-        .submitDeliverable("https://github.com/agent/work", "Completed with tests")
-        .accounts({
-          job: jobPDA,
-          agent: agent.publicKey,
-        })
-        .signers([agent])
-        .rpc();
+      const event = await captureEvent("deliverableSubmitted", () =>
+        program.methods
+          .submitDeliverable(0, deliverable("https://github.com/agent/work"), [])
+          .accounts({
+            job: jobPDA,
+            agent: agent.publicKey,
+          })
+          .signers([agent])
+          .rpc()
+      );
 
       const job = await program.account.job.fetch(jobPDA);
       assert.deepEqual(job.status, { underReview: {} });
-      assert.ok(job.deliverable.includes("https://github.com/agent/work"));
+      assert.equal(job.deliverable.uri, "https://github.com/agent/work");
+      assert.deepEqual(job.deliverable.contentHash, Array(32).fill(7));
+      assert.equal(job.artifactCount, 0);
+
+      assert.ok(event, "DeliverableSubmitted event not emitted");
+      assert.ok(event.job.equals(jobPDA));
+      assert.equal(event.jobId, JOB_ID);
+      assert.ok(event.agent.equals(agent.publicKey));
+      assert.equal(event.milestone, 0);
+      assert.deepEqual(event.contentHash, Array(32).fill(7));
+      assert.equal(event.uri, "https://github.com/agent/work");
+      assert.equal(event.artifactCount, 0);
     });
 
     it("SECURITY: unauthorized agent cannot submit deliverable", async () => {
@@ -385,7 +795,7 @@ describe("basilisk-escrow", () => {
       const [escrowToken2] = findEscrowTokenPDA(jobId2);
 
       await program.methods
-        .createJob(jobId2, new anchor.BN(1_000_000), "Security test", 7)
+        .createJob(jobId2, new anchor.BN(1_000_000), "Security test", 7, terms())
         .accounts({
           job: jobPDA2,
           escrowAuthority: escrowAuth2,
@@ -393,6 +803,8 @@ describe("basilisk-escrow", () => {
           requester: requester.publicKey,
           requesterToken: requesterToken,
           mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -412,7 +824,7 @@ describe("basilisk-escrow", () => {
       // Unauthorized user tries to submit
       try {
         await program.methods
-          .submitDeliverable("https://evil.com", "Fake work")
+          .submitDeliverable(0, deliverable("https://evil.com"), [])
           .accounts({
             job: jobPDA2,
             agent: unauthorizedUser.publicKey,
@@ -438,18 +850,25 @@ describe("basilisk-escrow", () => {
 
       const agentBalanceBefore = (await getAccount(provider.connection, agentToken)).amount;
 
-      await program.methods
-        .approveAndPay(5) // 5-star rating
-        .accounts({
-          job: jobPDA,
-          escrowAuthority: escrowAuthority,
-          requester: requester.publicKey,
-          escrowToken: escrowToken,
-          agentToken: agentToken,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([requester])
-        .rpc();
+      const event = await captureEvent("jobApproved", () =>
+        program.methods
+          .approveAndPay(0, 5) // 5-star rating
+          .accounts({
+            payout: {
+              job: jobPDA,
+              escrowAuthority: escrowAuthority,
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: escrowToken,
+              agentToken: agentToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            },
+            requester: requester.publicKey,
+          })
+          .signers([requester])
+          .rpc()
+      );
 
       const job = await program.account.job.fetch(jobPDA);
       assert.deepEqual(job.status, { completed: {} });
@@ -458,6 +877,18 @@ describe("basilisk-escrow", () => {
       // Verify agent received payment
       const agentBalanceAfter = (await getAccount(provider.connection, agentToken)).amount;
       assert.equal(Number(agentBalanceAfter) - Number(agentBalanceBefore), JOB_AMOUNT);
+
+      assert.ok(event, "JobApproved event not emitted");
+      assert.ok(event.job.equals(jobPDA));
+      assert.equal(event.jobId, JOB_ID);
+      assert.ok(event.agent.equals(agent.publicKey));
+      assert.equal(event.milestone, 0);
+      assert.equal(event.amount.toNumber(), JOB_AMOUNT);
+      assert.equal(event.fee.toNumber(), 0);
+      assert.equal(event.rating, 5);
+      assert.isTrue(event.completed);
+      assert.equal(event.bondReturned.toNumber(), 0);
+      assert.isFalse(event.autoApproved);
     });
 
     it("fails with invalid rating", async () => {
@@ -468,7 +899,7 @@ describe("basilisk-escrow", () => {
       const [escrowToken] = findEscrowTokenPDA(jobId);
 
       await program.methods
-        .createJob(jobId, new anchor.BN(1_000_000), "Rating test", 7)
+        .createJob(jobId, new anchor.BN(1_000_000), "Rating test", 7, terms())
         .accounts({
           job: jobPDA,
           escrowAuthority: escrowAuth,
@@ -476,6 +907,8 @@ describe("basilisk-escrow", () => {
           requester: requester.publicKey,
           requesterToken: requesterToken,
           mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -490,21 +923,26 @@ describe("basilisk-escrow", () => {
         .rpc();
 
       await program.methods
-        .submitDeliverable("https://work.com", "Done")
+        .submitDeliverable(0, deliverable("https://work.com"), [])
         .accounts({ job: jobPDA, agent: agent.publicKey })
         .signers([agent])
         .rpc();
 
       try {
         await program.methods
-          .approveAndPay(6) // Invalid: > 5
+          .approveAndPay(0, 6) // Invalid: > 5
           .accounts({
-            job: jobPDA,
-            escrowAuthority: escrowAuth,
+            payout: {
+              job: jobPDA,
+              escrowAuthority: escrowAuth,
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: escrowToken,
+              agentToken: agentToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            },
             requester: requester.publicKey,
-            escrowToken: escrowToken,
-            agentToken: agentToken,
-            tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([requester])
           .rpc();
@@ -522,14 +960,19 @@ describe("basilisk-escrow", () => {
 
       try {
         await program.methods
-          .approveAndPay(5)
+          .approveAndPay(0, 5)
           .accounts({
-            job: jobPDA,
-            escrowAuthority: escrowAuth,
+            payout: {
+              job: jobPDA,
+              escrowAuthority: escrowAuth,
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: escrowToken,
+              agentToken: agentToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            },
             requester: unauthorizedUser.publicKey,
-            escrowToken: escrowToken,
-            agentToken: agentToken,
-            tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([unauthorizedUser])
           .rpc();
@@ -554,7 +997,7 @@ describe("basilisk-escrow", () => {
       const amount = 5_000_000;
 
       await program.methods
-        .createJob(jobId, new anchor.BN(amount), "Cancel test", 7)
+        .createJob(jobId, new anchor.BN(amount), "Cancel test", 7, terms())
         .accounts({
           job: jobPDA,
           escrowAuthority: escrowAuth,
@@ -562,6 +1005,8 @@ describe("basilisk-escrow", () => {
           requester: requester.publicKey,
           requesterToken: requesterToken,
           mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -571,18 +1016,21 @@ describe("basilisk-escrow", () => {
 
       const balanceBefore = (await getAccount(provider.connection, requesterToken)).amount;
 
-      await program.methods
-        .cancelJob()
-        .accounts({
-          job: jobPDA,
-          escrowAuthority: escrowAuth,
-          requester: requester.publicKey,
-          escrowToken: escrowToken,
-          requesterToken: requesterToken,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([requester])
-        .rpc();
+      const event = await captureEvent("jobCancelled", () =>
+        program.methods
+          .cancelJob()
+          .accounts({
+            job: jobPDA,
+            escrowAuthority: escrowAuth,
+            requester: requester.publicKey,
+            escrowToken: escrowToken,
+            requesterToken: requesterToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([requester])
+          .rpc()
+      );
 
       const job = await program.account.job.fetch(jobPDA);
       assert.deepEqual(job.status, { cancelled: {} });
@@ -590,6 +1038,12 @@ describe("basilisk-escrow", () => {
       // Verify refund
       const balanceAfter = (await getAccount(provider.connection, requesterToken)).amount;
       assert.equal(Number(balanceAfter) - Number(balanceBefore), amount);
+
+      assert.ok(event, "JobCancelled event not emitted");
+      assert.ok(event.job.equals(jobPDA));
+      assert.equal(event.jobId, jobId);
+      assert.equal(event.refundAmount.toNumber(), amount);
+      assert.equal(event.bondForfeited.toNumber(), 0);
     });
 
     it("non-requester cannot cancel", async () => {
@@ -599,7 +1053,7 @@ describe("basilisk-escrow", () => {
       const [escrowToken] = findEscrowTokenPDA(jobId);
 
       await program.methods
-        .createJob(jobId, new anchor.BN(1_000_000), "Auth test", 7)
+        .createJob(jobId, new anchor.BN(1_000_000), "Auth test", 7, terms())
         .accounts({
           job: jobPDA,
           escrowAuthority: escrowAuth,
@@ -607,6 +1061,8 @@ describe("basilisk-escrow", () => {
           requester: requester.publicKey,
           requesterToken: requesterToken,
           mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -631,6 +1087,7 @@ describe("basilisk-escrow", () => {
             requester: unauthorizedUser.publicKey,
             escrowToken: escrowToken,
             requesterToken: unauthorizedToken,
+            mint: mint,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([unauthorizedUser])
@@ -648,6 +1105,7 @@ describe("basilisk-escrow", () => {
 
   describe("Dispute Resolution", () => {
     const disputeJobId = "dispute-test";
+    let rejected: any = null;
 
     before(async () => {
       // Create a job and get it to Disputed status
@@ -656,7 +1114,7 @@ describe("basilisk-escrow", () => {
       const [escrowToken] = findEscrowTokenPDA(disputeJobId);
 
       await program.methods
-        .createJob(disputeJobId, new anchor.BN(10_000_000), "Dispute test", 7)
+        .createJob(disputeJobId, new anchor.BN(10_000_000), "Dispute test", 7, terms())
         .accounts({
           job: jobPDA,
           escrowAuthority: escrowAuth,
@@ -664,6 +1122,8 @@ describe("basilisk-escrow", () => {
           requester: requester.publicKey,
           requesterToken: requesterToken,
           mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -678,16 +1138,18 @@ describe("basilisk-escrow", () => {
         .rpc();
 
       await program.methods
-        .submitDeliverable("https://work.com/result", "Work done")
+        .submitDeliverable(0, deliverable("https://work.com/result"), [])
         .accounts({ job: jobPDA, agent: agent.publicKey })
         .signers([agent])
         .rpc();
 
-      await program.methods
-        .rejectWork("Missing tests and documentation")
-        .accounts({ job: jobPDA, requester: requester.publicKey })
-        .signers([requester])
-        .rpc();
+      rejected = await captureEvent("jobRejected", () =>
+        program.methods
+          .rejectWork(0, "Missing tests and documentation", false)
+          .accounts({ job: jobPDA, requester: requester.publicKey })
+          .signers([requester])
+          .rpc()
+      );
     });
 
     it("job is in Disputed status after rejection", async () => {
@@ -695,6 +1157,13 @@ describe("basilisk-escrow", () => {
       const job = await program.account.job.fetch(jobPDA);
       assert.deepEqual(job.status, { disputed: {} });
       assert.ok(job.disputed);
+
+      assert.ok(rejected, "JobRejected event not emitted");
+      assert.ok(rejected.job.equals(jobPDA));
+      assert.equal(rejected.jobId, disputeJobId);
+      assert.equal(rejected.milestone, 0);
+      assert.equal(rejected.reason, "Missing tests and documentation");
+      assert.equal(rejected.disputeDeadline.toNumber(), job.disputeDeadline.toNumber());
     });
 
     it("SECURITY: unauthorized user CANNOT resolve dispute", async () => {
@@ -705,16 +1174,24 @@ describe("basilisk-escrow", () => {
 
       try {
         await program.methods
-          .resolveDispute(100) // Try to send 100% to "agent" (which could be attacker's account)
+          .resolveDispute(100, 0) // Try to send 100% to "agent" (which could be attacker's account)
           .accounts({
-            job: jobPDA,
+            payout: {
+              job: jobPDA,
+              escrowAuthority: escrowAuth,
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: escrowToken,
+              agentToken: agentToken,
+              requesterToken: requesterToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+              agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+            },
             config: configPDA,
-            escrowAuthority: escrowAuth,
+            disputeVote: findDisputeVotePDA(jobPDA)[0],
             arbitrator: unauthorizedUser.publicKey, // NOT the authorized arbitrator
-            escrowToken: escrowToken,
-            agentToken: agentToken,
-            requesterToken: requesterToken,
-            tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([unauthorizedUser])
           .rpc();
@@ -737,20 +1214,30 @@ describe("basilisk-escrow", () => {
       const expectedAgentAmount = Math.floor(10_000_000 * 60 / 100);
       const expectedRequesterAmount = 10_000_000 - expectedAgentAmount;
 
-      await program.methods
-        .resolveDispute(agentPercentage)
-        .accounts({
-          job: jobPDA,
-          config: configPDA,
-          escrowAuthority: escrowAuth,
-          arbitrator: arbitrator.publicKey,
-          escrowToken: escrowToken,
-          agentToken: agentToken,
-          requesterToken: requesterToken,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([arbitrator])
-        .rpc();
+      const event = await captureEvent("disputeResolved", () =>
+        program.methods
+          .resolveDispute(agentPercentage, 0)
+          .accounts({
+            payout: {
+              job: jobPDA,
+              escrowAuthority: escrowAuth,
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: escrowToken,
+              agentToken: agentToken,
+              requesterToken: requesterToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+              agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+            },
+            config: configPDA,
+            disputeVote: findDisputeVotePDA(jobPDA)[0],
+            arbitrator: arbitrator.publicKey,
+          })
+          .signers([arbitrator])
+          .rpc()
+      );
 
       const job = await program.account.job.fetch(jobPDA);
       assert.deepEqual(job.status, { resolved: {} });
@@ -770,6 +1257,17 @@ describe("basilisk-escrow", () => {
         expectedRequesterAmount,
         "Requester should receive 40%"
       );
+
+      assert.ok(event, "DisputeResolved event not emitted");
+      assert.ok(event.job.equals(jobPDA));
+      assert.equal(event.jobId, disputeJobId);
+      assert.equal(event.agentAmount.toNumber(), expectedAgentAmount);
+      assert.equal(event.requesterAmount.toNumber(), expectedRequesterAmount);
+      assert.equal(event.fee.toNumber(), 0);
+      assert.equal(event.arbitrationFee.toNumber(), 0);
+      assert.equal(event.agentPercentage, agentPercentage);
+      assert.equal(event.bondAgentAmount.toNumber(), 0);
+      assert.equal(event.bondRequesterAmount.toNumber(), 0);
     });
 
     it("cannot resolve non-disputed job", async () => {
@@ -781,16 +1279,24 @@ describe("basilisk-escrow", () => {
 
       try {
         await program.methods
-          .resolveDispute(50)
+          .resolveDispute(50, 0)
           .accounts({
-            job: jobPDA,
+            payout: {
+              job: jobPDA,
+              escrowAuthority: escrowAuth,
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: escrowToken,
+              agentToken: agentToken,
+              requesterToken: requesterToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+              agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+            },
             config: configPDA,
-            escrowAuthority: escrowAuth,
+            disputeVote: findDisputeVotePDA(jobPDA)[0],
             arbitrator: arbitrator.publicKey,
-            escrowToken: escrowToken,
-            agentToken: agentToken,
-            requesterToken: requesterToken,
-            tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([arbitrator])
           .rpc();
@@ -822,14 +1328,19 @@ describe("basilisk-escrow", () => {
 
       try {
         await program.methods
-          .approveAndPay(5)
+          .approveAndPay(0, 5)
           .accounts({
-            job: jobPDA,
-            escrowAuthority: escrowAuth,
+            payout: {
+              job: jobPDA,
+              escrowAuthority: escrowAuth,
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: escrowToken,
+              agentToken: attackerToken, // Wrong owner!
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            },
             requester: requester.publicKey,
-            escrowToken: escrowToken,
-            agentToken: attackerToken, // Wrong owner!
-            tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([requester])
           .rpc();
@@ -854,14 +1365,19 @@ describe("basilisk-escrow", () => {
 
       try {
         await program.methods
-          .approveAndPay(5)
+          .approveAndPay(0, 5)
           .accounts({
-            job: jobPDA,
-            escrowAuthority: escrowAuth,
+            payout: {
+              job: jobPDA,
+              escrowAuthority: escrowAuth,
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: fakeEscrow, // Wrong escrow!
+              agentToken: agentToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            },
             requester: requester.publicKey,
-            escrowToken: fakeEscrow, // Wrong escrow!
-            agentToken: agentToken,
-            tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([requester])
           .rpc();
@@ -877,14 +1393,14 @@ describe("basilisk-escrow", () => {
 
       // First submit deliverable so job is UnderReview
       await program.methods
-        .submitDeliverable("https://work.com", "Work")
+        .submitDeliverable(0, deliverable("https://work.com"), [])
         .accounts({ job: jobPDA, agent: agent.publicKey })
         .signers([agent])
         .rpc();
 
       try {
         await program.methods
-          .rejectWork("Fake rejection")
+          .rejectWork(0, "Fake rejection", false)
           .accounts({
             job: jobPDA,
             requester: unauthorizedUser.publicKey,
@@ -913,7 +1429,7 @@ describe("basilisk-escrow", () => {
       const amount = 5_000_000;
 
       await program.methods
-        .createJob(jobId, new anchor.BN(amount), "Refund test", 7)
+        .createJob(jobId, new anchor.BN(amount), "Refund test", 7, terms())
         .accounts({
           job: jobPDA,
           escrowAuthority: escrowAuth,
@@ -921,6 +1437,8 @@ describe("basilisk-escrow", () => {
           requester: requester.publicKey,
           requesterToken: requesterToken,
           mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -932,27 +1450,35 @@ describe("basilisk-escrow", () => {
         .accounts({ job: jobPDA, agent: agent.publicKey })
         .signers([agent]).rpc();
 
-      await program.methods.submitDeliverable("https://x.com", "Work")
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
         .accounts({ job: jobPDA, agent: agent.publicKey })
         .signers([agent]).rpc();
 
-      await program.methods.rejectWork("Terrible")
+      await program.methods.rejectWork(0, "Terrible", false)
         .accounts({ job: jobPDA, requester: requester.publicKey })
         .signers([requester]).rpc();
 
       const requesterBefore = (await getAccount(provider.connection, requesterToken)).amount;
 
       await program.methods
-        .resolveDispute(0) // 0% to agent = full refund
+        .resolveDispute(0, 0) // 0% to agent = full refund
         .accounts({
-          job: jobPDA,
+          payout: {
+            job: jobPDA,
+            escrowAuthority: escrowAuth,
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: escrowToken,
+            agentToken: agentToken,
+            requesterToken: requesterToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+            agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+          },
           config: configPDA,
-          escrowAuthority: escrowAuth,
+          disputeVote: findDisputeVotePDA(jobPDA)[0],
           arbitrator: arbitrator.publicKey,
-          escrowToken: escrowToken,
-          agentToken: agentToken,
-          requesterToken: requesterToken,
-          tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([arbitrator])
         .rpc();
@@ -971,7 +1497,7 @@ describe("basilisk-escrow", () => {
       const amount = 5_000_000;
 
       await program.methods
-        .createJob(jobId, new anchor.BN(amount), "Agent test", 7)
+        .createJob(jobId, new anchor.BN(amount), "Agent test", 7, terms())
         .accounts({
           job: jobPDA,
           escrowAuthority: escrowAuth,
@@ -979,6 +1505,8 @@ describe("basilisk-escrow", () => {
           requester: requester.publicKey,
           requesterToken: requesterToken,
           mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -990,27 +1518,35 @@ describe("basilisk-escrow", () => {
         .accounts({ job: jobPDA, agent: agent.publicKey })
         .signers([agent]).rpc();
 
-      await program.methods.submitDeliverable("https://x.com", "Work")
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
         .accounts({ job: jobPDA, agent: agent.publicKey })
         .signers([agent]).rpc();
 
-      await program.methods.rejectWork("Disagree")
+      await program.methods.rejectWork(0, "Disagree", false)
         .accounts({ job: jobPDA, requester: requester.publicKey })
         .signers([requester]).rpc();
 
       const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
 
       await program.methods
-        .resolveDispute(100) // 100% to agent
+        .resolveDispute(100, 0) // 100% to agent
         .accounts({
-          job: jobPDA,
+          payout: {
+            job: jobPDA,
+            escrowAuthority: escrowAuth,
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: escrowToken,
+            agentToken: agentToken,
+            requesterToken: requesterToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+            agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+          },
           config: configPDA,
-          escrowAuthority: escrowAuth,
+          disputeVote: findDisputeVotePDA(jobPDA)[0],
           arbitrator: arbitrator.publicKey,
-          escrowToken: escrowToken,
-          agentToken: agentToken,
-          requesterToken: requesterToken,
-          tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([arbitrator])
         .rpc();
@@ -1027,7 +1563,7 @@ describe("basilisk-escrow", () => {
       const [configPDA] = findConfigPDA();
 
       await program.methods
-        .createJob(jobId, new anchor.BN(1_000_000), "Overflow test", 7)
+        .createJob(jobId, new anchor.BN(1_000_000), "Overflow test", 7, terms())
         .accounts({
           job: jobPDA,
           escrowAuthority: escrowAuth,
@@ -1035,6 +1571,8 @@ describe("basilisk-escrow", () => {
           requester: requester.publicKey,
           requesterToken: requesterToken,
           mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -1046,33 +1584,3307 @@ describe("basilisk-escrow", () => {
         .accounts({ job: jobPDA, agent: agent.publicKey })
         .signers([agent]).rpc();
 
-      await program.methods.submitDeliverable("https://x.com", "Work")
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
         .accounts({ job: jobPDA, agent: agent.publicKey })
         .signers([agent]).rpc();
 
-      await program.methods.rejectWork("Bad")
+      await program.methods.rejectWork(0, "Bad", false)
         .accounts({ job: jobPDA, requester: requester.publicKey })
         .signers([requester]).rpc();
 
       try {
         await program.methods
-          .resolveDispute(101) // > 100 is invalid, but u8 max is 255
+          .resolveDispute(101, 0) // > 100 is invalid, but u8 max is 255
           .accounts({
-            job: jobPDA,
+            payout: {
+              job: jobPDA,
+              escrowAuthority: escrowAuth,
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: escrowToken,
+              agentToken: agentToken,
+              requesterToken: requesterToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+              agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+            },
             config: configPDA,
-            escrowAuthority: escrowAuth,
+            disputeVote: findDisputeVotePDA(jobPDA)[0],
             arbitrator: arbitrator.publicKey,
+          })
+          .signers([arbitrator])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidPercentage");
+      }
+    });
+  });
+
+  // ====================================================================
+  // 11. CLOSE JOB TESTS
+  // ====================================================================
+
+  describe("CloseJob", () => {
+    it("requester closes cancelled job and reclaims rent", async () => {
+      const jobId = "cancel-test"; // Cancelled in CancelJob tests
+      const [jobPDA] = findJobPDA(jobId);
+      const [escrowAuth] = findEscrowAuthorityPDA(jobId);
+      const [escrowToken] = findEscrowTokenPDA(jobId);
+
+      const lamportsBefore = await provider.connection.getBalance(requester.publicKey);
+
+      await program.methods
+        .closeJob()
+        .accounts({
+          job: jobPDA,
+          artifacts: findArtifactsPDA(jobPDA)[0],
+          escrowAuthority: escrowAuth,
+          requester: requester.publicKey,
+          escrowToken: escrowToken,
+          requesterToken: requesterToken,
+          archive: null,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+
+      assert.isNull(await provider.connection.getAccountInfo(jobPDA));
+      assert.isNull(await provider.connection.getAccountInfo(escrowToken));
+
+      const lamportsAfter = await provider.connection.getBalance(requester.publicKey);
+      assert.ok(lamportsAfter > lamportsBefore, "Rent should be returned to requester");
+    });
+
+    it("requester closes resolved job and keeps an archive record", async () => {
+      const jobId = "full-refund-test"; // Resolved in Edge Cases tests
+      const [jobPDA] = findJobPDA(jobId);
+      const [escrowAuth] = findEscrowAuthorityPDA(jobId);
+      const [escrowToken] = findEscrowTokenPDA(jobId);
+      const { createdAt } = await program.account.job.fetch(jobPDA);
+      const [archivePDA] = findArchivePDA(jobPDA, createdAt);
+      const close = () =>
+        program.methods
+          .closeJob()
+          .accounts({
+            job: jobPDA,
+            artifacts: findArtifactsPDA(jobPDA)[0],
+            escrowAuthority: escrowAuth,
+            requester: requester.publicKey,
             escrowToken: escrowToken,
-            agentToken: agentToken,
             requesterToken: requesterToken,
+            archive: archivePDA,
+            mint: mint,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
-          .signers([arbitrator])
+          .signers([requester])
           .rpc();
+
+      // The agent gets to rate the requester before the job can be closed
+      try {
+        await close();
         assert.fail("Should have failed");
       } catch (err) {
-        expect(err.toString()).to.contain("InvalidPercentage");
+        expect(err.toString()).to.contain("RatingWindowOpen");
+      }
+      await rateSettledJob(jobPDA);
+      await close();
+
+      assert.isNull(await provider.connection.getAccountInfo(jobPDA));
+
+      const archive = await program.account.jobArchive.fetch(archivePDA);
+      assert.ok(archive.job.equals(jobPDA));
+      assert.equal(archive.jobId, jobId);
+      assert.deepEqual(archive.status, { resolved: {} });
+      assert.equal(archive.amount.toNumber(), 5_000_000);
+      assert.equal(archive.requesterRating, 4);
+    });
+
+    it("a job re-created with the same job_id can be archived again", async () => {
+      const jobId = "rearchive-test";
+      const [jobPDA] = findJobPDA(jobId);
+      const [escrowAuthority] = findEscrowAuthorityPDA(jobId);
+      const [escrowToken] = findEscrowTokenPDA(jobId);
+
+      const createCancelAndArchive = async () => {
+        await program.methods
+          .createJob(jobId, new anchor.BN(1_000_000), "Re-archive test", 7, terms())
+          .accounts({
+            job: jobPDA,
+            escrowAuthority,
+            escrowToken,
+            requester: requester.publicKey,
+            requesterToken: requesterToken,
+            mint: mint,
+            config: findConfigPDA()[0],
+            treasuryToken: treasuryToken,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+          })
+          .signers([requester])
+          .rpc();
+        await program.methods
+          .cancelJob()
+          .accounts({
+            job: jobPDA,
+            escrowAuthority,
+            requester: requester.publicKey,
+            escrowToken,
+            requesterToken: requesterToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([requester])
+          .rpc();
+
+        const { createdAt } = await program.account.job.fetch(jobPDA);
+        const [archivePDA] = findArchivePDA(jobPDA, createdAt);
+        await program.methods
+          .closeJob()
+          .accounts({
+            job: jobPDA,
+            artifacts: findArtifactsPDA(jobPDA)[0],
+            escrowAuthority,
+            requester: requester.publicKey,
+            escrowToken,
+            requesterToken: requesterToken,
+            archive: archivePDA,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([requester])
+          .rpc();
+        return archivePDA;
+      };
+
+      const first = await createCancelAndArchive();
+      // A later second gives the new job a different created_at
+      await new Promise((resolve) => setTimeout(resolve, 1500));
+      const second = await createCancelAndArchive();
+
+      assert.notOk(first.equals(second));
+      for (const archivePDA of [first, second]) {
+        const archive = await program.account.jobArchive.fetch(archivePDA);
+        assert.ok(archive.job.equals(jobPDA));
+        assert.deepEqual(archive.status, { cancelled: {} });
+      }
+    });
+
+    it("cannot close a job that is still in review", async () => {
+      const jobId = "rating-test"; // Still UnderReview
+      const [jobPDA] = findJobPDA(jobId);
+      const [escrowAuth] = findEscrowAuthorityPDA(jobId);
+      const [escrowToken] = findEscrowTokenPDA(jobId);
+
+      try {
+        await program.methods
+          .closeJob()
+          .accounts({
+            job: jobPDA,
+            artifacts: findArtifactsPDA(jobPDA)[0],
+            escrowAuthority: escrowAuth,
+            requester: requester.publicKey,
+            escrowToken: escrowToken,
+            requesterToken: requesterToken,
+            archive: null,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([requester])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("JobNotTerminal");
       }
     });
   });
+
+  // ====================================================================
+  // 12. PROTOCOL FEE TESTS
+  // ====================================================================
+
+  describe("Protocol Fees", () => {
+    const feeJobId = "fee-test";
+    const FEE_BPS = 250; // 2.5%
+
+    after(async () => {
+      // Reset fee so it does not leak into other suites
+      await program.methods
+        .updateConfig(null, null, 0, null, null, null, null)
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    });
+
+    it("rejects fee above maximum", async () => {
+      try {
+        await program.methods
+          .updateConfig(null, null, 1_001, null, null, null, null)
+          .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("FeeTooHigh");
+      }
+    });
+
+    it("deducts the fee into the treasury on approval", async () => {
+      const [configPDA] = findConfigPDA();
+      await program.methods
+        .updateConfig(null, null, FEE_BPS, null, null, null, null)
+        .accounts({ config: configPDA, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+      const [jobPDA] = findJobPDA(feeJobId);
+      const [escrowAuth] = findEscrowAuthorityPDA(feeJobId);
+      const [escrowToken] = findEscrowTokenPDA(feeJobId);
+      const amount = 4_000_000;
+      const expectedFee = Math.floor((amount * FEE_BPS) / 10_000);
+
+      await program.methods
+        .createJob(feeJobId, new anchor.BN(amount), "Fee test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: escrowAuth,
+          escrowToken: escrowToken,
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: configPDA,
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
+      const treasuryBefore = (await getAccount(provider.connection, treasuryToken)).amount;
+
+      await program.methods
+        .approveAndPay(0, 5)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: escrowAuth,
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: escrowToken,
+            agentToken: agentToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+
+      const agentAfter = (await getAccount(provider.connection, agentToken)).amount;
+      const treasuryAfter = (await getAccount(provider.connection, treasuryToken)).amount;
+      assert.equal(Number(agentAfter) - Number(agentBefore), amount - expectedFee);
+      assert.equal(Number(treasuryAfter) - Number(treasuryBefore), expectedFee);
+    });
+
+    it("admin withdraws fees from the treasury", async () => {
+      adminToken = await createAccount(
+        provider.connection,
+        admin,
+        mint,
+        admin.publicKey
+      );
+      const treasuryBalance = (await getAccount(provider.connection, treasuryToken)).amount;
+
+      await program.methods
+        .withdrawFees(new anchor.BN(treasuryBalance.toString()))
+        .accounts({
+          config: findConfigPDA()[0],
+          admin: admin.publicKey,
+          treasuryToken: treasuryToken,
+          destinationToken: adminToken,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+
+      const adminBalance = (await getAccount(provider.connection, adminToken)).amount;
+      assert.equal(Number(adminBalance), Number(treasuryBalance));
+    });
+
+    it("SECURITY: non-admin cannot withdraw fees", async () => {
+      const attackerToken = await createAccount(
+        provider.connection,
+        unauthorizedUser,
+        mint,
+        unauthorizedUser.publicKey,
+        Keypair.generate()
+      );
+
+      try {
+        await program.methods
+          .withdrawFees(new anchor.BN(1))
+          .accounts({
+            config: findConfigPDA()[0],
+            admin: unauthorizedUser.publicKey,
+            treasuryToken: treasuryToken,
+            destinationToken: attackerToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([unauthorizedUser])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+  });
+
+  // ====================================================================
+  // 13. TOKEN-2022 TESTS
+  // ====================================================================
+
+  describe("Token-2022", () => {
+    const TRANSFER_FEE_BPS = 100; // 1% transfer fee on the mint itself
+    let feeMint: PublicKey;
+    let requesterToken22: PublicKey;
+    let agentToken22: PublicKey;
+
+    before(async () => {
+      // Create a Token-2022 mint with the transfer-fee extension
+      const mintKeypair = Keypair.generate();
+      const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+      const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+
+      const tx = new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: admin.publicKey,
+          newAccountPubkey: mintKeypair.publicKey,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          mintKeypair.publicKey,
+          admin.publicKey,
+          admin.publicKey,
+          TRANSFER_FEE_BPS,
+          BigInt(1_000_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          mintKeypair.publicKey,
+          6,
+          admin.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        )
+      );
+      await sendAndConfirmTransaction(provider.connection, tx, [admin, mintKeypair]);
+      feeMint = mintKeypair.publicKey;
+
+      requesterToken22 = await createAccount(
+        provider.connection, requester, feeMint, requester.publicKey,
+        undefined, undefined, TOKEN_2022_PROGRAM_ID
+      );
+      agentToken22 = await createAccount(
+        provider.connection, agent, feeMint, agent.publicKey,
+        undefined, undefined, TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        provider.connection, admin, feeMint, requesterToken22, admin,
+        100_000_000, [], undefined, TOKEN_2022_PROGRAM_ID
+      );
+    });
+
+    it("escrows the net amount received for a transfer-fee mint and pays out", async () => {
+      const jobId = "token22-job";
+      const [jobPDA] = findJobPDA(jobId);
+      const [escrowAuth] = findEscrowAuthorityPDA(jobId);
+      const [escrowToken] = findEscrowTokenPDA(jobId);
+      const [treasury22] = findTreasuryPDA(feeMint);
+
+      const amount = 10_000_000;
+      const transferFee = Math.floor((amount * TRANSFER_FEE_BPS) / 10_000);
+
+      await program.methods
+        .createJob(jobId, new anchor.BN(amount), "Token-2022 test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: escrowAuth,
+          escrowToken: escrowToken,
+          requester: requester.publicKey,
+          requesterToken: requesterToken22,
+          mint: feeMint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasury22,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.equal(job.amount.toNumber(), amount - transferFee);
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      await program.methods
+        .approveAndPay(0, 5)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: escrowAuth,
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: escrowToken,
+            agentToken: agentToken22,
+            treasuryToken: treasury22,
+            mint: feeMint,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          },
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+
+      const escrow = await getAccount(
+        provider.connection, escrowToken, undefined, TOKEN_2022_PROGRAM_ID
+      );
+      assert.equal(Number(escrow.amount), 0);
+
+      // Closing harvests withheld transfer fees so the escrow can be closed
+      await rateSettledJob(jobPDA);
+      await program.methods
+        .closeJob()
+        .accounts({
+          job: jobPDA,
+          artifacts: findArtifactsPDA(jobPDA)[0],
+          escrowAuthority: escrowAuth,
+          requester: requester.publicKey,
+          escrowToken: escrowToken,
+          requesterToken: requesterToken22,
+          archive: null,
+          mint: feeMint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+
+      assert.isNull(await provider.connection.getAccountInfo(escrowToken));
+    });
+  });
+
+  // ====================================================================
+  // 14. NATIVE SOL TESTS
+  // ====================================================================
+
+  describe("Native SOL", () => {
+    const FEE_BPS = 250; // 2.5%
+    const AMOUNT = anchor.web3.LAMPORTS_PER_SOL / 2;
+
+    async function createSolJob(jobId: string) {
+      await program.methods
+        .createSolJob(jobId, new anchor.BN(AMOUNT), "Native SOL test", 7, terms())
+        .accounts({
+          job: findJobPDA(jobId)[0],
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          solVault: findSolVaultPDA(jobId)[0],
+          config: findConfigPDA()[0],
+          solTreasury: findSolTreasuryPDA()[0],
+          requester: requester.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+    }
+
+    before(async () => {
+      await program.methods
+        .updateConfig(null, null, FEE_BPS, null, null, null, null)
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateConfig(null, null, 0, null, null, null, null)
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    });
+
+    it("escrows lamports in the SOL vault", async () => {
+      const jobId = "sol-job-001";
+      await createSolJob(jobId);
+
+      const job = await program.account.job.fetch(findJobPDA(jobId)[0]);
+      assert.deepEqual(job.asset, { nativeSol: {} });
+      assert.equal(job.amount.toNumber(), AMOUNT);
+      assert.ok(job.mint.equals(PublicKey.default));
+
+      const vault = await provider.connection.getAccountInfo(findSolVaultPDA(jobId)[0]);
+      assert.isAtLeast(vault.lamports, AMOUNT);
+    });
+
+    it("pays the agent in lamports minus the protocol fee", async () => {
+      const jobId = "sol-job-001";
+      const [jobPDA] = findJobPDA(jobId);
+      const [solTreasury] = findSolTreasuryPDA();
+      const expectedFee = Math.floor((AMOUNT * FEE_BPS) / 10_000);
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      const agentBefore = await provider.connection.getBalance(agent.publicKey);
+      const treasuryBefore = await provider.connection.getBalance(solTreasury);
+
+      await program.methods
+        .approveAndPay(0, 5)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            solVault: findSolVaultPDA(jobId)[0],
+            agent: agent.publicKey,
+            solTreasury: solTreasury,
+          },
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+
+      const agentAfter = await provider.connection.getBalance(agent.publicKey);
+      const treasuryAfter = await provider.connection.getBalance(solTreasury);
+      assert.equal(agentAfter - agentBefore, AMOUNT - expectedFee);
+      assert.equal(treasuryAfter - treasuryBefore, expectedFee);
+    });
+
+    it("SECURITY: SOL payout to the wrong wallet is rejected", async () => {
+      const jobId = "sol-job-002";
+      const [jobPDA] = findJobPDA(jobId);
+      await createSolJob(jobId);
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      try {
+        await program.methods
+          .approveAndPay(0, 5)
+          .accounts({
+            payout: {
+              job: jobPDA,
+              escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              solVault: findSolVaultPDA(jobId)[0],
+              agent: unauthorizedUser.publicKey,
+              solTreasury: findSolTreasuryPDA()[0],
+            },
+            requester: requester.publicKey,
+          })
+          .signers([requester])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidRecipient");
+      }
+    });
+
+    it("refunds lamports on cancel and closes the vault", async () => {
+      const jobId = "sol-job-003";
+      const [jobPDA] = findJobPDA(jobId);
+      const [solVault] = findSolVaultPDA(jobId);
+      await createSolJob(jobId);
+
+      await program.methods
+        .cancelJob()
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          requester: requester.publicKey,
+          solVault: solVault,
+        })
+        .signers([requester])
+        .rpc();
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { cancelled: {} });
+
+      await program.methods
+        .closeJob()
+        .accounts({
+          job: jobPDA,
+          artifacts: findArtifactsPDA(jobPDA)[0],
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          requester: requester.publicKey,
+          archive: null,
+          solVault: solVault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+
+      assert.isNull(await provider.connection.getAccountInfo(jobPDA));
+      assert.isNull(await provider.connection.getAccountInfo(solVault));
+    });
+
+    it("admin withdraws SOL fees but not the treasury's rent", async () => {
+      const [solTreasury] = findSolTreasuryPDA();
+      const info = await provider.connection.getAccountInfo(solTreasury);
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(info.data.length);
+      const available = info.lamports - rent;
+
+      try {
+        await program.methods
+          .withdrawSolFees(new anchor.BN(available + 1))
+          .accounts({
+            config: findConfigPDA()[0],
+            admin: admin.publicKey,
+            solTreasury: solTreasury,
+            destination: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InsufficientTreasuryBalance");
+      }
+
+      await program.methods
+        .withdrawSolFees(new anchor.BN(available))
+        .accounts({
+          config: findConfigPDA()[0],
+          admin: admin.publicKey,
+          solTreasury: solTreasury,
+          destination: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      assert.equal(await provider.connection.getBalance(solTreasury), rent);
+    });
+  });
+
+  // ====================================================================
+  // 15. MILESTONE TESTS
+  // ====================================================================
+
+  describe("Milestones", () => {
+    const jobId = "milestone-job";
+    const FIRST = 3_000_000;
+    const SECOND = 2_000_000;
+
+    function milestone(amount: number, deadlineDays: number | null = null) {
+      return {
+        amount: new anchor.BN(amount),
+        descriptionHash: Array(32).fill(amount % 256),
+        deadlineDays,
+      };
+    }
+
+    function createMilestoneJob(id: string, amount: number, milestones: any[]) {
+      return program.methods
+        .createJob(id, new anchor.BN(amount), "Milestone test", 7, terms({ milestones }))
+        .accounts({
+          job: findJobPDA(id)[0],
+          escrowAuthority: findEscrowAuthorityPDA(id)[0],
+          escrowToken: findEscrowTokenPDA(id)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+    }
+
+    // The job is rated once, with its final milestone.
+    function approve(id: string, index: number, rating = index === 1 ? 5 : 0) {
+      return program.methods
+        .approveAndPay(index, rating)
+        .accounts({
+          payout: {
+            job: findJobPDA(id)[0],
+            escrowAuthority: findEscrowAuthorityPDA(id)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: findEscrowTokenPDA(id)[0],
+            agentToken: agentToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+    }
+
+    it("fails when milestone amounts do not sum to the job amount", async () => {
+      try {
+        await createMilestoneJob("milestone-bad", FIRST, [milestone(FIRST), milestone(SECOND)]);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("MilestoneAmountMismatch");
+      }
+    });
+
+    it("fails when a milestone is due after the job deadline", async () => {
+      try {
+        await createMilestoneJob("milestone-late", FIRST, [milestone(FIRST, 8)]);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidMilestoneDeadline");
+      }
+    });
+
+    it("creates a job with a milestone schedule", async () => {
+      await createMilestoneJob(jobId, FIRST + SECOND, [milestone(FIRST, 3), milestone(SECOND)]);
+
+      const job = await program.account.job.fetch(findJobPDA(jobId)[0]);
+      assert.equal(job.milestones.length, 2);
+      assert.equal(job.milestones[0].amount.toNumber(), FIRST);
+      assert.isAbove(job.milestones[0].deadline.toNumber(), 0);
+      assert.equal(job.milestones[1].deadline.toNumber(), 0);
+      assert.equal(job.currentMilestone, 0);
+    });
+
+    it("releases the first milestone and returns the job to InProgress", async () => {
+      const [jobPDA] = findJobPDA(jobId);
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      await program.methods.submitDeliverable(0, deliverable("https://x.com/1"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      try {
+        await approve(jobId, 0, 5);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidRating");
+      }
+
+      const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
+      await approve(jobId, 0);
+      const agentAfter = (await getAccount(provider.connection, agentToken)).amount;
+      assert.equal(Number(agentAfter) - Number(agentBefore), FIRST);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { inProgress: {} });
+      assert.equal(job.rating, 0);
+      assert.equal(job.currentMilestone, 1);
+      assert.equal(job.releasedAmount.toNumber(), FIRST);
+      assert.isTrue(job.milestones[0].released);
+    });
+
+    it("rejects a submission for the wrong milestone", async () => {
+      try {
+        await program.methods.submitDeliverable(0, deliverable("https://x.com/1"), [])
+          .accounts({ job: findJobPDA(jobId)[0], agent: agent.publicKey })
+          .signers([agent]).rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidMilestone");
+      }
+    });
+
+    it("completes the job when the final milestone is paid", async () => {
+      const [jobPDA] = findJobPDA(jobId);
+
+      await program.methods.submitDeliverable(1, deliverable("https://x.com/2"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      await approve(jobId, 1);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { completed: {} });
+      assert.equal(job.rating, 5);
+      assert.equal(job.releasedAmount.toNumber(), FIRST + SECOND);
+
+      const escrow = await getAccount(provider.connection, findEscrowTokenPDA(jobId)[0]);
+      assert.equal(Number(escrow.amount), 0);
+    });
+
+    it("a dispute only splits the rejected milestone and the job moves on", async () => {
+      const id = "milestone-dispute";
+      const [jobPDA] = findJobPDA(id);
+      const [escrowToken] = findEscrowTokenPDA(id);
+      await createMilestoneJob(id, FIRST + SECOND, [milestone(FIRST), milestone(SECOND)]);
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.submitDeliverable(0, deliverable("https://x.com/1"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.rejectWork(0, "First part incomplete", false)
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+
+      const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
+      const requesterBefore = (await getAccount(provider.connection, requesterToken)).amount;
+
+      await program.methods
+        .resolveDispute(60, 0)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: findEscrowAuthorityPDA(id)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken,
+            agentToken: agentToken,
+            requesterToken: requesterToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+            agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+          },
+          config: findConfigPDA()[0],
+          disputeVote: findDisputeVotePDA(jobPDA)[0],
+          arbitrator: arbitrator.publicKey,
+        })
+        .signers([arbitrator])
+        .rpc();
+
+      // Only the first milestone is split 60/40
+      const agentAfter = (await getAccount(provider.connection, agentToken)).amount;
+      const requesterAfter = (await getAccount(provider.connection, requesterToken)).amount;
+      assert.equal(Number(agentAfter) - Number(agentBefore), FIRST * 0.6);
+      assert.equal(Number(requesterAfter) - Number(requesterBefore), FIRST * 0.4);
+
+      // The second milestone stays escrowed for the agent to deliver
+      const escrow = await getAccount(provider.connection, escrowToken);
+      assert.equal(Number(escrow.amount), SECOND);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { inProgress: {} });
+      assert.equal(job.currentMilestone, 1);
+      assert.equal(job.releasedAmount.toNumber(), FIRST);
+      assert.isTrue(job.milestones[0].released);
+      assert.isFalse(job.disputed);
+
+      await program.methods.submitDeliverable(1, deliverable("https://x.com/2"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await approve(id, 1);
+
+      const completed = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(completed.status, { completed: {} });
+    });
+  });
+
+  // ====================================================================
+  // 16. APPLICATION TESTS
+  // ====================================================================
+
+  describe("Applications", () => {
+    const jobId = "application-job";
+    const applicants = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    let jobPDA: PublicKey;
+
+    before(async () => {
+      for (const applicant of applicants) {
+        const sig = await provider.connection.requestAirdrop(
+          applicant.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(sig);
+      }
+
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(1_000_000), "Application test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+    });
+
+    it("agents apply with a price, ETA and pitch hash", async () => {
+      for (const [i, applicant] of applicants.entries()) {
+        await program.methods
+          .applyToJob(new anchor.BN(900_000 + i), 3 + i, Array(32).fill(i))
+          .accounts({
+            job: jobPDA,
+            application: findApplicationPDA(jobPDA, applicant.publicKey)[0],
+            agent: applicant.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([applicant])
+          .rpc();
+      }
+
+      const application = await program.account.application.fetch(
+        findApplicationPDA(jobPDA, applicants[1].publicKey)[0]
+      );
+      const job = await program.account.job.fetch(jobPDA);
+      assert.ok(application.job.equals(jobPDA));
+      assert.equal(application.jobCreatedAt.toNumber(), job.createdAt.toNumber());
+      assert.equal(application.proposedAmount.toNumber(), 900_001);
+      assert.equal(application.etaDays, 4);
+    });
+
+    it("applicant withdraws their application", async () => {
+      const [application] = findApplicationPDA(jobPDA, applicants[2].publicKey);
+      await program.methods
+        .withdrawApplication()
+        .accounts({ application, agent: applicants[2].publicKey })
+        .signers([applicants[2]])
+        .rpc();
+
+      assert.isNull(await provider.connection.getAccountInfo(application));
+    });
+
+    it("SECURITY: non-requester cannot select an agent", async () => {
+      const chosen = applicants[0];
+      try {
+        await program.methods
+          .selectAgent()
+          .accounts({
+            job: jobPDA,
+            application: findApplicationPDA(jobPDA, chosen.publicKey)[0],
+            agent: chosen.publicKey,
+            requester: unauthorizedUser.publicKey,
+          })
+          .signers([unauthorizedUser])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("requester selects an agent and closes the losing applications", async () => {
+      const [chosen, loser] = applicants;
+      const [chosenApp] = findApplicationPDA(jobPDA, chosen.publicKey);
+      const [loserApp] = findApplicationPDA(jobPDA, loser.publicKey);
+      const loserBefore = await provider.connection.getBalance(loser.publicKey);
+
+      await program.methods
+        .selectAgent()
+        .accounts({
+          job: jobPDA,
+          application: chosenApp,
+          agent: chosen.publicKey,
+          requester: requester.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: loserApp, isWritable: true, isSigner: false },
+          { pubkey: loser.publicKey, isWritable: true, isSigner: false },
+        ])
+        .signers([requester])
+        .rpc();
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.ok(job.agent.equals(chosen.publicKey));
+      assert.deepEqual(job.status, { inProgress: {} });
+
+      assert.isNull(await provider.connection.getAccountInfo(chosenApp));
+      assert.isNull(await provider.connection.getAccountInfo(loserApp));
+      assert.isAbove(await provider.connection.getBalance(loser.publicKey), loserBefore);
+    });
+
+    it("cannot apply once an agent is selected", async () => {
+      const late = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        late.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      try {
+        await program.methods
+          .applyToJob(new anchor.BN(1), 1, Array(32).fill(0))
+          .accounts({
+            job: jobPDA,
+            application: findApplicationPDA(jobPDA, late.publicKey)[0],
+            agent: late.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([late])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("JobNotOpen");
+      }
+    });
+
+    it("SECURITY: an application left over from a closed job cannot be selected", async () => {
+      const reusedId = "reused-application-job";
+      const [reusedPDA] = findJobPDA(reusedId);
+      const [escrowAuthority] = findEscrowAuthorityPDA(reusedId);
+      const [escrowToken] = findEscrowTokenPDA(reusedId);
+      const stale = applicants[2];
+      const [staleApp] = findApplicationPDA(reusedPDA, stale.publicKey);
+      const create = (jobTerms) =>
+        program.methods
+          .createJob(reusedId, new anchor.BN(1_000_000), "Reused job", 7, jobTerms)
+          .accounts({
+            job: reusedPDA,
+            escrowAuthority,
+            escrowToken,
+            requester: requester.publicKey,
+            requesterToken: requesterToken,
+            mint: mint,
+            config: findConfigPDA()[0],
+            treasuryToken: treasuryToken,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+          })
+          .signers([requester])
+          .rpc();
+
+      await create(terms());
+      await program.methods
+        .applyToJob(new anchor.BN(1_000_000), 3, Array(32).fill(7))
+        .accounts({
+          job: reusedPDA,
+          application: staleApp,
+          agent: stale.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stale])
+        .rpc();
+
+      await program.methods
+        .cancelJob()
+        .accounts({
+          job: reusedPDA,
+          escrowAuthority,
+          requester: requester.publicKey,
+          escrowToken,
+          requesterToken: requesterToken,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([requester])
+        .rpc();
+      await program.methods
+        .closeJob()
+        .accounts({
+          job: reusedPDA,
+          artifacts: findArtifactsPDA(reusedPDA)[0],
+          escrowAuthority,
+          requester: requester.publicKey,
+          archive: null,
+          escrowToken,
+          requesterToken: requesterToken,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+
+      // Re-create the same job_id as a private job the stale applicant is
+      // not invited to, in a later second so created_at differs
+      await new Promise((resolve) => setTimeout(resolve, 1500));
+      await create(terms({ allowedAgents: [applicants[0].publicKey] }));
+
+      try {
+        await program.methods
+          .selectAgent()
+          .accounts({
+            job: reusedPDA,
+            application: staleApp,
+            agent: stale.publicKey,
+            requester: requester.publicKey,
+          })
+          .signers([requester])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidApplication");
+      }
+    });
+  });
+
+  // ====================================================================
+  // 17. AGENT BOND TESTS
+  // ====================================================================
+
+  describe("Agent Bond", () => {
+    const AMOUNT = 1_000_000;
+    const BOND = 200_000;
+
+    function createBondedJob(
+      jobId: string,
+      withBondToken = true,
+      deadlineDays = 7,
+      bond: Record<string, any> = { bondAmount: new anchor.BN(BOND) }
+    ) {
+      return program.methods
+        .createJob(jobId, new anchor.BN(AMOUNT), "Bond test", deadlineDays, terms(bond))
+        .accounts({
+          job: findJobPDA(jobId)[0],
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          bondToken: withBondToken ? findBondTokenPDA(jobId)[0] : null,
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+    }
+
+    function acceptWithBond(jobId: string) {
+      return program.methods
+        .acceptJob()
+        .accounts({
+          job: findJobPDA(jobId)[0],
+          agent: agent.publicKey,
+          bondToken: findBondTokenPDA(jobId)[0],
+          agentToken: agentToken,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+    }
+
+    before(async () => {
+      await mintTo(provider.connection, admin, mint, agentToken, admin, 10 * BOND);
+    });
+
+    it("rejects a bonded job without a bond escrow", async () => {
+      try {
+        await createBondedJob("bond-missing", false);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("MissingAccount");
+      }
+    });
+
+    it("SECURITY: an agent cannot post a bond on an already-expired job", async () => {
+      // A zero-day deadline used to create a job that was expired on
+      // arrival: accepting it posted the bond, and cancel_job then handed
+      // the requester a full refund plus the forfeited bond.
+      const jobId = "bond-expired";
+      const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
+      try {
+        await createBondedJob(jobId, true, 0);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidDeadline");
+      }
+
+      try {
+        await acceptWithBond(jobId);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.not.contain("Should have failed");
+      }
+      const agentAfter = (await getAccount(provider.connection, agentToken)).amount;
+      assert.equal(Number(agentAfter), Number(agentBefore));
+    });
+
+    it("agent posts the bond on acceptance", async () => {
+      const jobId = "bond-job-001";
+      await createBondedJob(jobId);
+
+      const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
+      await acceptWithBond(jobId);
+      const agentAfter = (await getAccount(provider.connection, agentToken)).amount;
+
+      const job = await program.account.job.fetch(findJobPDA(jobId)[0]);
+      assert.deepEqual(job.status, { inProgress: {} });
+      assert.equal(job.bondBalance.toNumber(), BOND);
+      assert.equal(Number(agentBefore) - Number(agentAfter), BOND);
+
+      const bond = await getAccount(provider.connection, findBondTokenPDA(jobId)[0]);
+      assert.equal(Number(bond.amount), BOND);
+    });
+
+    it("rejects a job with both a fixed and a percentage bond", async () => {
+      try {
+        await createBondedJob("bond-both", true, 7, {
+          bondAmount: new anchor.BN(BOND),
+          bondBps: 500,
+        });
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidBond");
+      }
+    });
+
+    it("agent posts a percentage bond of the escrowed amount", async () => {
+      const jobId = "bond-bps";
+      await createBondedJob(jobId, true, 7, { bondBps: 2_500 });
+
+      const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
+      await acceptWithBond(jobId);
+      const agentAfter = (await getAccount(provider.connection, agentToken)).amount;
+
+      const job = await program.account.job.fetch(findJobPDA(jobId)[0]);
+      assert.equal(job.bondBps, 2_500);
+      assert.equal(job.bondAmount.toNumber(), AMOUNT / 4);
+      assert.equal(job.bondBalance.toNumber(), AMOUNT / 4);
+      assert.equal(Number(agentBefore) - Number(agentAfter), AMOUNT / 4);
+    });
+
+    it("bond is returned to the agent with the final payment", async () => {
+      const jobId = "bond-job-001";
+      const [jobPDA] = findJobPDA(jobId);
+
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
+      await program.methods
+        .approveAndPay(0, 5)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: findEscrowTokenPDA(jobId)[0],
+            agentToken: agentToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            bondToken: findBondTokenPDA(jobId)[0],
+          },
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+      const agentAfter = (await getAccount(provider.connection, agentToken)).amount;
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { completed: {} });
+      assert.equal(job.bondBalance.toNumber(), 0);
+      assert.equal(Number(agentAfter) - Number(agentBefore), AMOUNT + BOND);
+    });
+
+    it("arbitrator splits the bond when resolving a dispute", async () => {
+      const jobId = "bond-job-002";
+      const [jobPDA] = findJobPDA(jobId);
+      await createBondedJob(jobId);
+      await acceptWithBond(jobId);
+
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.rejectWork(0, "Incomplete", false)
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+
+      const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
+      const requesterBefore = (await getAccount(provider.connection, requesterToken)).amount;
+
+      await program.methods
+        .resolveDispute(50, 25)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: findEscrowTokenPDA(jobId)[0],
+            agentToken: agentToken,
+            requesterToken: requesterToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            bondToken: findBondTokenPDA(jobId)[0],
+            requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+            agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+          },
+          config: findConfigPDA()[0],
+          disputeVote: findDisputeVotePDA(jobPDA)[0],
+          arbitrator: arbitrator.publicKey,
+        })
+        .signers([arbitrator])
+        .rpc();
+
+      const agentAfter = (await getAccount(provider.connection, agentToken)).amount;
+      const requesterAfter = (await getAccount(provider.connection, requesterToken)).amount;
+      assert.equal(Number(agentAfter) - Number(agentBefore), AMOUNT / 2 + BOND / 4);
+      assert.equal(
+        Number(requesterAfter) - Number(requesterBefore),
+        AMOUNT / 2 + (BOND * 3) / 4
+      );
+
+      const bond = await getAccount(provider.connection, findBondTokenPDA(jobId)[0]);
+      assert.equal(Number(bond.amount), 0);
+    });
+
+    it("requester releases a selected agent who never posts the bond", async () => {
+      const jobId = "bond-unselect";
+      const [jobPDA] = findJobPDA(jobId);
+      const reserved = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        reserved.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+      await createBondedJob(jobId);
+
+      await program.methods
+        .applyToJob(new anchor.BN(AMOUNT), 3, Array(32).fill(1))
+        .accounts({
+          job: jobPDA,
+          application: findApplicationPDA(jobPDA, reserved.publicKey)[0],
+          agent: reserved.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([reserved])
+        .rpc();
+      await program.methods
+        .selectAgent()
+        .accounts({
+          job: jobPDA,
+          application: findApplicationPDA(jobPDA, reserved.publicKey)[0],
+          agent: reserved.publicKey,
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+
+      // Reserved but unbonded: nobody else can take the job yet
+      try {
+        await acceptWithBond(jobId);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("JobAlreadyTaken");
+      }
+
+      try {
+        await program.methods.unselectAgent()
+          .accounts({ job: jobPDA, requester: unauthorizedUser.publicKey })
+          .signers([unauthorizedUser]).rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+
+      await program.methods.unselectAgent()
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+
+      let job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { open: {} });
+      assert.ok(job.agent.equals(PublicKey.default));
+
+      await acceptWithBond(jobId);
+      job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { inProgress: {} });
+      assert.ok(job.agent.equals(agent.publicKey));
+
+      // Once the bond is posted the agent can no longer be unselected
+      try {
+        await program.methods.unselectAgent()
+          .accounts({ job: jobPDA, requester: requester.publicKey })
+          .signers([requester]).rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("JobNotOpen");
+      }
+    });
+
+    it("close_job closes the bond escrow too", async () => {
+      const jobId = "bond-job-002";
+      const [jobPDA] = findJobPDA(jobId);
+      await rateSettledJob(jobPDA);
+
+      await program.methods
+        .closeJob()
+        .accounts({
+          job: jobPDA,
+          artifacts: findArtifactsPDA(jobPDA)[0],
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          requester: requester.publicKey,
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requesterToken: requesterToken,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          bondToken: findBondTokenPDA(jobId)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+
+      assert.isNull(await provider.connection.getAccountInfo(findBondTokenPDA(jobId)[0]));
+    });
+  });
+
+  // ====================================================================
+  // 18. AGENT PROFILE TESTS
+  // ====================================================================
+
+  describe("Agent Profile", () => {
+    const jobId = "profile-job";
+    const AMOUNT = 1_000_000;
+    const newAgent = Keypair.generate();
+    let newAgentToken: PublicKey;
+    let jobPDA: PublicKey;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(
+        newAgent.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+      newAgentToken = await createAccount(provider.connection, newAgent, mint, newAgent.publicKey);
+
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(AMOUNT), "Profile test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+    });
+
+    it("creates the agent's profile on their first acceptance", async () => {
+      const [profilePDA] = findAgentProfilePDA(newAgent.publicKey);
+      assert.isNull(await provider.connection.getAccountInfo(profilePDA));
+
+      await program.methods
+        .acceptJob()
+        .accounts({
+          job: jobPDA,
+          agent: newAgent.publicKey,
+          agentProfile: profilePDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([newAgent])
+        .rpc();
+
+      const profile = await program.account.agentProfile.fetch(profilePDA);
+      assert.ok(profile.agent.equals(newAgent.publicKey));
+      assert.equal(profile.jobsCompleted, 0);
+      assert.equal(profile.volume.length, 0);
+    });
+
+    it("SECURITY: approval rejects another agent's profile", async () => {
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: newAgent.publicKey })
+        .signers([newAgent]).rpc();
+
+      try {
+        await program.methods
+          .approveAndPay(0, 4)
+          .accounts({
+            payout: {
+              job: jobPDA,
+              escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: findEscrowTokenPDA(jobId)[0],
+              agentToken: newAgentToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            },
+            requester: requester.publicKey,
+          })
+          .signers([requester])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("ConstraintSeeds");
+      }
+    });
+
+    it("records completion, rating and volume on approval", async () => {
+      const [profilePDA] = findAgentProfilePDA(newAgent.publicKey);
+
+      await program.methods
+        .approveAndPay(0, 4)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+            agentProfile: profilePDA,
+            escrowToken: findEscrowTokenPDA(jobId)[0],
+            agentToken: newAgentToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+
+      const profile = await program.account.agentProfile.fetch(profilePDA);
+      assert.equal(profile.jobsCompleted, 1);
+      assert.equal(profile.ratingSum.toNumber(), 4);
+      assert.equal(profile.ratingsCount, 1);
+      assert.equal(profile.volume.length, 1);
+      assert.ok(profile.volume[0].mint.equals(mint));
+      assert.equal(profile.volume[0].amount.toNumber(), AMOUNT);
+    });
+
+    it("records dispute outcomes", async () => {
+      const [profilePDA] = findAgentProfilePDA(agent.publicKey);
+      const profile = await program.account.agentProfile.fetch(profilePDA);
+      // Dispute Resolution (60%) and Edge Cases (0%, 100%) resolved for `agent`
+      assert.isAtLeast(profile.disputesWon, 2);
+      assert.isAtLeast(profile.disputesLost, 1);
+    });
+  });
+
+  // ====================================================================
+  // 19. REQUESTER RATING TESTS
+  // ====================================================================
+
+  describe("Requester Rating", () => {
+    const jobId = "rate-requester-job";
+    let jobPDA: PublicKey;
+
+    function rateRequester(signer: Keypair, rating: number) {
+      return program.methods
+        .rateRequester(rating)
+        .accounts({
+          job: jobPDA,
+          requesterProfile: findRequesterProfilePDA()[0],
+          agent: signer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(1_000_000), "Rating test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+    });
+
+    it("cannot rate before the job is settled", async () => {
+      try {
+        await rateRequester(agent, 5);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidStatus");
+      }
+    });
+
+    it("agent rates the requester after completion", async () => {
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods
+        .approveAndPay(0, 5)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: findEscrowTokenPDA(jobId)[0],
+            agentToken: agentToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+
+      // Earlier suites already rated this requester before closing their jobs
+      const [profilePDA] = findRequesterProfilePDA();
+      const before = await program.account.requesterProfile.fetch(profilePDA);
+      await rateRequester(agent, 2);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.equal(job.requesterRating, 2);
+
+      const profile = await program.account.requesterProfile.fetch(profilePDA);
+      assert.ok(profile.requester.equals(requester.publicKey));
+      assert.equal(profile.ratingsCount, before.ratingsCount + 1);
+      assert.equal(profile.ratingSum.toNumber(), before.ratingSum.toNumber() + 2);
+    });
+
+    it("SECURITY: only the assigned agent can rate", async () => {
+      try {
+        await rateRequester(unauthorizedUser, 1);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("cannot rate the same job twice", async () => {
+      try {
+        await rateRequester(agent, 5);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("AlreadyRated");
+      }
+    });
+  });
+
+  // ====================================================================
+  // 20. ARBITRATOR PANEL TESTS
+  // ====================================================================
+
+  describe("Arbitrator Panel", () => {
+    const jobId = "panel-dispute-job";
+    const AMOUNT = 1_000_000;
+    const arbitrator2 = Keypair.generate();
+    const arbitrator3 = Keypair.generate();
+    let jobPDA: PublicKey;
+
+    function vote(
+      signer: Keypair,
+      agentPercentage: number,
+      votePayer: PublicKey | null = null,
+      id = jobId,
+      arbitratorToken: PublicKey | null = null,
+      feePayees: PublicKey[] = []
+    ) {
+      const [job] = findJobPDA(id);
+      return program.methods
+        .resolveDispute(agentPercentage, 0)
+        .accounts({
+          payout: {
+            job,
+            escrowAuthority: findEscrowAuthorityPDA(id)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: findEscrowTokenPDA(id)[0],
+            agentToken: agentToken,
+            requesterToken: requesterToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            requesterEvidence: findEvidencePDA(job, requester.publicKey)[0],
+            agentEvidence: findEvidencePDA(job, agent.publicKey)[0],
+          },
+          config: findConfigPDA()[0],
+          arbitrator: signer.publicKey,
+          disputeVote: findDisputeVotePDA(job)[0],
+          arbitratorToken,
+          votePayer,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          feePayees.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+        )
+        .signers([signer])
+        .rpc();
+    }
+
+    async function disputedJob(id: string, jobTerms: any) {
+      const [job] = findJobPDA(id);
+      await program.methods
+        .createJob(id, new anchor.BN(AMOUNT), "Panel test", 7, jobTerms)
+        .accounts({
+          job,
+          escrowAuthority: findEscrowAuthorityPDA(id)[0],
+          escrowToken: findEscrowTokenPDA(id)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.rejectWork(0, "Disputed", false)
+        .accounts({ job, requester: requester.publicKey })
+        .signers([requester]).rpc();
+    }
+
+    before(async () => {
+      for (const member of [arbitrator2, arbitrator3]) {
+        const sig = await provider.connection.requestAirdrop(
+          member.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(sig);
+      }
+
+      await program.methods
+        .updateConfig(
+          [arbitrator.publicKey, arbitrator2.publicKey, arbitrator3.publicKey],
+          null,
+          null,
+          2,
+          null,
+          null,
+          null
+        )
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+      [jobPDA] = findJobPDA(jobId);
+      await disputedJob(jobId, terms());
+    });
+
+    after(async () => {
+      await program.methods
+        .updateConfig([arbitrator.publicKey], null, null, 1, null, null, null)
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    });
+
+    it("a single vote below the threshold only records the vote", async () => {
+      await vote(arbitrator2, 80);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { disputed: {} });
+
+      const record = await program.account.disputeVote.fetch(findDisputeVotePDA(jobPDA)[0]);
+      assert.ok(record.payer.equals(arbitrator2.publicKey));
+      assert.equal(record.votes.length, 1);
+      assert.ok(record.votes[0].arbitrator.equals(arbitrator2.publicKey));
+      assert.equal(record.votes[0].agentPercentage, 80);
+    });
+
+    it("an arbitrator cannot vote twice", async () => {
+      try {
+        await vote(arbitrator2, 100);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("AlreadyVoted");
+      }
+    });
+
+    it("reaching the threshold executes the median split", async () => {
+      const agentBefore = (await getAccount(provider.connection, agentToken)).amount;
+      const requesterBefore = (await getAccount(provider.connection, requesterToken)).amount;
+
+      try {
+        await vote(arbitrator, 40);
+        assert.fail("Should have failed");
+      } catch (err) {
+        // The vote record's rent belongs to arbitrator2, who voted first
+        expect(err.toString()).to.contain("MissingAccount");
+      }
+
+      const payerBefore = await provider.connection.getBalance(arbitrator2.publicKey);
+      const rent = (await provider.connection.getAccountInfo(findDisputeVotePDA(jobPDA)[0])).lamports;
+      await vote(arbitrator, 40, arbitrator2.publicKey);
+
+      assert.equal(
+        await provider.connection.getBalance(arbitrator2.publicKey),
+        payerBefore + rent
+      );
+
+      // Lower median of [40, 80]
+      const agentAfter = (await getAccount(provider.connection, agentToken)).amount;
+      const requesterAfter = (await getAccount(provider.connection, requesterToken)).amount;
+      assert.equal(Number(agentAfter) - Number(agentBefore), (AMOUNT * 40) / 100);
+      assert.equal(Number(requesterAfter) - Number(requesterBefore), (AMOUNT * 60) / 100);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { resolved: {} });
+      assert.isNull(await provider.connection.getAccountInfo(findDisputeVotePDA(jobPDA)[0]));
+    });
+
+    it("the arbitration fee is shared by the arbitrators whose votes counted", async () => {
+      const feeJobId = "panel-fee-job";
+      const FEE = 1_001;
+      await disputedJob(
+        feeJobId,
+        terms({
+          arbitrationFee: {
+            kind: { fixed: {} },
+            value: new anchor.BN(FEE),
+            bearer: { split: {} },
+          },
+        })
+      );
+
+      const [token1, token2] = await Promise.all(
+        [arbitrator, arbitrator2].map((member) =>
+          createAccount(provider.connection, member, mint, member.publicKey, Keypair.generate())
+        )
+      );
+
+      await vote(arbitrator2, 100, null, feeJobId);
+
+      try {
+        // The second voter's fee destination is missing
+        await vote(arbitrator, 100, arbitrator2.publicKey, feeJobId, token1);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("MissingAccount");
+      }
+
+      try {
+        // A fee destination not held by the voter it is paid for
+        await vote(arbitrator, 100, arbitrator2.publicKey, feeJobId, token1, [token1]);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidTokenOwner");
+      }
+
+      await vote(arbitrator, 100, arbitrator2.publicKey, feeJobId, token1, [token2]);
+
+      // Executing arbitrator takes the rounding remainder
+      const share = Math.floor(FEE / 2);
+      assert.equal(Number((await getAccount(provider.connection, token2)).amount), share);
+      assert.equal(Number((await getAccount(provider.connection, token1)).amount), FEE - share);
+      assert.isNull(
+        await provider.connection.getAccountInfo(findDisputeVotePDA(findJobPDA(feeJobId)[0])[0])
+      );
+    });
+  });
+
+  // ====================================================================
+  // 21. PER-JOB ARBITRATOR TESTS
+  // ====================================================================
+
+  describe("Per-Job Arbitrator", () => {
+    const AMOUNT = 1_000_000;
+    const specialist = Keypair.generate();
+
+    function createJobWithArbitrator(jobId: string, withRegistration: boolean) {
+      return program.methods
+        .createJob(jobId, new anchor.BN(AMOUNT), "Specialist test", 7, terms({ arbitrator: specialist.publicKey }))
+        .accounts({
+          job: findJobPDA(jobId)[0],
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          arbitratorRegistration: withRegistration
+            ? findArbitratorRegistrationPDA(specialist.publicKey)[0]
+            : null,
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+    }
+
+    function resolveAs(jobId: string, signer: Keypair) {
+      const [jobPDA] = findJobPDA(jobId);
+      return program.methods
+        .resolveDispute(70, 0)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: findEscrowTokenPDA(jobId)[0],
+            agentToken: agentToken,
+            requesterToken: requesterToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+            agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+          },
+          config: findConfigPDA()[0],
+          arbitrator: signer.publicKey,
+          disputeVote: findDisputeVotePDA(jobPDA)[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(
+        specialist.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      await program.methods
+        .updateConfig(null, null, null, null, true, null, null)
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateConfig(null, null, null, null, false, null, null)
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    });
+
+    it("rejects an unregistered arbitrator when the registry is required", async () => {
+      try {
+        await createJobWithArbitrator("specialist-unregistered", false);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("MissingAccount");
+      }
+    });
+
+    it("SECURITY: only the admin can register arbitrators", async () => {
+      try {
+        await program.methods
+          .registerArbitrator(specialist.publicKey)
+          .accounts({
+            config: findConfigPDA()[0],
+            registration: findArbitratorRegistrationPDA(specialist.publicKey)[0],
+            admin: unauthorizedUser.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([unauthorizedUser])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("stores a registered arbitrator on the job", async () => {
+      await program.methods
+        .registerArbitrator(specialist.publicKey)
+        .accounts({
+          config: findConfigPDA()[0],
+          registration: findArbitratorRegistrationPDA(specialist.publicKey)[0],
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      await createJobWithArbitrator("specialist-job", true);
+
+      const job = await program.account.job.fetch(findJobPDA("specialist-job")[0]);
+      assert.ok(job.arbitrator.equals(specialist.publicKey));
+    });
+
+    it("only the job's arbitrator can resolve its dispute", async () => {
+      const jobId = "specialist-job";
+      const [jobPDA] = findJobPDA(jobId);
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.rejectWork(0, "Disputed", false)
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+
+      try {
+        await resolveAs(jobId, arbitrator);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("UnauthorizedArbitrator");
+      }
+
+      await resolveAs(jobId, specialist);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { resolved: {} });
+    });
+
+    it("admin deregisters the arbitrator", async () => {
+      const [registration] = findArbitratorRegistrationPDA(specialist.publicKey);
+      await program.methods
+        .deregisterArbitrator()
+        .accounts({
+          config: findConfigPDA()[0],
+          registration,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      assert.isNull(await provider.connection.getAccountInfo(registration));
+    });
+  });
+
+  // ====================================================================
+  // 22. ARBITRATION FEE TESTS
+  // ====================================================================
+
+  describe("Arbitration Fee", () => {
+    const AMOUNT = 1_000_000;
+    let arbitratorToken: PublicKey;
+
+    async function disputedJob(jobId: string, jobTerms: any) {
+      const [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(AMOUNT), "Arbitration fee test", 7, jobTerms)
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.rejectWork(0, "Disputed", false)
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+    }
+
+    async function balances() {
+      return Promise.all(
+        [agentToken, requesterToken, arbitratorToken].map(async (account) =>
+          Number((await getAccount(provider.connection, account)).amount)
+        )
+      );
+    }
+
+    function resolve(jobId: string, agentPercentage: number) {
+      const [jobPDA] = findJobPDA(jobId);
+      return program.methods
+        .resolveDispute(agentPercentage, 0)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: findEscrowTokenPDA(jobId)[0],
+            agentToken: agentToken,
+            requesterToken: requesterToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+            agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+          },
+          config: findConfigPDA()[0],
+          arbitrator: arbitrator.publicKey,
+          disputeVote: findDisputeVotePDA(jobPDA)[0],
+          arbitratorToken: arbitratorToken,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([arbitrator])
+        .rpc();
+    }
+
+    before(async () => {
+      arbitratorToken = await createAccount(
+        provider.connection,
+        arbitrator,
+        mint,
+        arbitrator.publicKey
+      );
+
+      // 5% of the disputed escrow, borne by whoever lost
+      await program.methods
+        .updateConfig(null, null, null, null, null, {
+          kind: { bps: {} },
+          value: new anchor.BN(500),
+          bearer: { loserPays: {} },
+        }, null)
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateConfig(null, null, null, null, null, {
+          kind: { fixed: {} },
+          value: new anchor.BN(0),
+          bearer: { loserPays: {} },
+        }, null)
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    });
+
+    it("rejects a bps arbitration fee above the maximum", async () => {
+      try {
+        await program.methods
+          .updateConfig(null, null, null, null, null, {
+            kind: { bps: {} },
+            value: new anchor.BN(1_001),
+            bearer: { split: {} },
+          }, null)
+          .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("FeeTooHigh");
+      }
+    });
+
+    it("loser pays the config fee in proportion to the share lost", async () => {
+      const jobId = "arb-fee-loser";
+      await disputedJob(jobId, terms());
+
+      const job = await program.account.job.fetch(findJobPDA(jobId)[0]);
+      assert.deepEqual(job.arbitrationFee.kind, { bps: {} });
+
+      const [agentBefore, requesterBefore, arbitratorBefore] = await balances();
+      await resolve(jobId, 75);
+      const [agentAfter, requesterAfter, arbitratorAfter] = await balances();
+
+      // 50_000 fee: agent lost 25% and pays 12_500, requester lost 75% and pays 37_500
+      assert.equal(arbitratorAfter - arbitratorBefore, 50_000);
+      assert.equal(agentAfter - agentBefore, 750_000 - 12_500);
+      assert.equal(requesterAfter - requesterBefore, 250_000 - 37_500);
+    });
+
+    it("a per-job fixed fee split evenly overrides the config", async () => {
+      const jobId = "arb-fee-split";
+      await disputedJob(
+        jobId,
+        terms({
+          arbitrationFee: {
+            kind: { fixed: {} },
+            value: new anchor.BN(10_000),
+            bearer: { split: {} },
+          },
+        })
+      );
+
+      const [agentBefore, requesterBefore, arbitratorBefore] = await balances();
+      await resolve(jobId, 50);
+      const [agentAfter, requesterAfter, arbitratorAfter] = await balances();
+
+      assert.equal(arbitratorAfter - arbitratorBefore, 10_000);
+      assert.equal(agentAfter - agentBefore, 495_000);
+      assert.equal(requesterAfter - requesterBefore, 495_000);
+    });
+  });
+
+  // ====================================================================
+  // 23. REVIEW TIMEOUT TESTS
+  // ====================================================================
+
+  describe("Review Timeout", () => {
+    const AMOUNT = 1_000_000;
+
+    function createJob(jobId: string, jobTerms: any) {
+      return program.methods
+        .createJob(jobId, new anchor.BN(AMOUNT), "Review timeout test", 7, jobTerms)
+        .accounts({
+          job: findJobPDA(jobId)[0],
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+    }
+
+    it("defaults to a 7 day review window", async () => {
+      const jobId = "review-default";
+      await createJob(jobId, terms());
+
+      const job = await program.account.job.fetch(findJobPDA(jobId)[0]);
+      assert.equal(job.reviewPeriod.toNumber(), 7 * 86400);
+      assert.equal(job.submittedAt.toNumber(), 0);
+    });
+
+    it("rejects a zero-day review window", async () => {
+      try {
+        await createJob("review-zero", terms({ reviewDays: 0 }));
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidReviewWindow");
+      }
+    });
+
+    it("cannot be claimed while the review window is open", async () => {
+      const jobId = "review-open";
+      const [jobPDA] = findJobPDA(jobId);
+      await createJob(jobId, terms({ reviewDays: 1 }));
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.equal(job.reviewPeriod.toNumber(), 86400);
+      assert.isAbove(job.submittedAt.toNumber(), 0);
+
+      // No signer is required; only the elapsed window is checked
+      try {
+        await program.methods
+          .claimAfterReviewTimeout(0)
+          .accounts({
+            payout: {
+              job: jobPDA,
+              escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: findEscrowTokenPDA(jobId)[0],
+              agentToken: agentToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            },
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("ReviewWindowOpen");
+      }
+
+      // The requester can still respond within the window
+      await program.methods.rejectWork(0, "Not done", false)
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+      const rejected = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(rejected.status, { disputed: {} });
+    });
+  });
+
+  // ====================================================================
+  // 24. DISPUTE TIMEOUT TESTS
+  // ====================================================================
+
+  describe("Dispute Timeout", () => {
+    const jobId = "dispute-timeout";
+    const AMOUNT = 1_000_000;
+    let jobPDA: PublicKey;
+
+    before(async () => {
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(AMOUNT), "Dispute timeout test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+    });
+
+    it("defaults to a 14 day window with a 50/50 split", async () => {
+      const config = await program.account.programConfig.fetch(findConfigPDA()[0]);
+      assert.equal(config.disputeFallback.days, 14);
+      assert.equal(config.disputeFallback.agentPercentage, 50);
+      assert.equal(config.disputeFallback.bondAgentPercentage, 50);
+    });
+
+    it("rejects a fallback split above 100%", async () => {
+      try {
+        await program.methods
+          .updateConfig(null, null, null, null, null, null, {
+            days: 14,
+            agentPercentage: 101,
+            bondAgentPercentage: 50,
+          })
+          .accounts({ config: findConfigPDA()[0], admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidPercentage");
+      }
+    });
+
+    it("records the dispute deadline when work is rejected", async () => {
+      await program.methods.rejectWork(0, "Disputed", false)
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+
+      const job = await program.account.job.fetch(jobPDA);
+      const now = Math.floor(Date.now() / 1000);
+      assert.approximately(job.disputeDeadline.toNumber(), now + 14 * 86400, 120);
+    });
+
+    it("cannot be expired before the deadline", async () => {
+      try {
+        await program.methods
+          .expireDispute()
+          .accounts({
+            payout: {
+              job: jobPDA,
+              escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: findEscrowTokenPDA(jobId)[0],
+              agentToken: agentToken,
+              requesterToken: requesterToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+              agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+            },
+            config: findConfigPDA()[0],
+            caller: provider.wallet.publicKey,
+            disputeVote: findDisputeVotePDA(jobPDA)[0],
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("DisputeWindowOpen");
+      }
+    });
+  });
+
+  // ====================================================================
+  // 25. DISPUTE EVIDENCE TESTS
+  // ====================================================================
+
+  describe("Dispute Evidence", () => {
+    const jobId = "evidence-job";
+    const AMOUNT = 1_000_000;
+    let jobPDA: PublicKey;
+
+    function submitEvidence(party: Keypair, n: number, uri = `ipfs://evidence-${n}`) {
+      return program.methods
+        .submitEvidence(Array(32).fill(n), uri)
+        .accounts({
+          job: jobPDA,
+          evidence: findEvidencePDA(jobPDA, party.publicKey)[0],
+          party: party.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([party])
+        .rpc();
+    }
+
+    before(async () => {
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(AMOUNT), "Evidence test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+    });
+
+    it("rejects evidence before a dispute is opened", async () => {
+      try {
+        await submitEvidence(agent, 0);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("NotDisputed");
+      }
+
+      await program.methods.rejectWork(0, "Incomplete", false)
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+    });
+
+    it("records each party's evidence in its own account", async () => {
+      await submitEvidence(requester, 1);
+      await submitEvidence(agent, 11);
+      await submitEvidence(agent, 12);
+
+      const requesterEvidence = await program.account.evidence.fetch(
+        findEvidencePDA(jobPDA, requester.publicKey)[0]
+      );
+      assert.ok(requesterEvidence.party.equals(requester.publicKey));
+      assert.equal(requesterEvidence.items.length, 1);
+
+      const agentEvidence = await program.account.evidence.fetch(
+        findEvidencePDA(jobPDA, agent.publicKey)[0]
+      );
+      assert.equal(agentEvidence.items.length, 2);
+      assert.deepEqual(agentEvidence.items[1].contentHash, Array(32).fill(12));
+      assert.equal(agentEvidence.items[1].uri, "ipfs://evidence-12");
+    });
+
+    it("SECURITY: rejects evidence from outside the job", async () => {
+      try {
+        await submitEvidence(unauthorizedUser, 99);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("rejects an overlong URI and more than 5 items", async () => {
+      try {
+        await submitEvidence(agent, 13, "ipfs://" + "x".repeat(130));
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("EvidenceUriTooLong");
+      }
+
+      for (let i = 13; i <= 15; i++) {
+        await submitEvidence(agent, i);
+      }
+      try {
+        await submitEvidence(agent, 16);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("TooManyEvidenceItems");
+      }
+    });
+
+    it("closes both evidence records when the dispute is resolved", async () => {
+      const [requesterEvidence] = findEvidencePDA(jobPDA, requester.publicKey);
+      const [agentEvidence] = findEvidencePDA(jobPDA, agent.publicKey);
+      const resolve = (wallets: { agent?: PublicKey; requester?: PublicKey }) =>
+        program.methods
+          .resolveDispute(50, 0)
+          .accounts({
+            payout: {
+              job: jobPDA,
+              escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+              agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+              escrowToken: findEscrowTokenPDA(jobId)[0],
+              agentToken: agentToken,
+              requesterToken: requesterToken,
+              treasuryToken: treasuryToken,
+              mint: mint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              agent: wallets.agent ?? null,
+              requester: wallets.requester ?? null,
+              requesterEvidence,
+              agentEvidence,
+            },
+            config: findConfigPDA()[0],
+            arbitrator: arbitrator.publicKey,
+            disputeVote: findDisputeVotePDA(jobPDA)[0],
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([arbitrator])
+          .rpc();
+
+      // The records cannot be left behind by omitting the parties' wallets
+      try {
+        await resolve({ agent: agent.publicKey });
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("MissingAccount");
+      }
+
+      await resolve({ agent: agent.publicKey, requester: requester.publicKey });
+
+      assert.isNull(await provider.connection.getAccountInfo(requesterEvidence));
+      assert.isNull(await provider.connection.getAccountInfo(agentEvidence));
+    });
+  });
+
+  // ====================================================================
+  // 26. DEADLINE EXTENSION TESTS
+  // ====================================================================
+
+  describe("Deadline Extension", () => {
+    const jobId = "extension-job";
+    let jobPDA: PublicKey;
+    let newDeadline: anchor.BN;
+
+    function propose(party: Keypair, deadline: anchor.BN) {
+      return program.methods
+        .proposeDeadlineExtension(deadline)
+        .accounts({ job: jobPDA, party: party.publicKey })
+        .signers([party])
+        .rpc();
+    }
+
+    function accept(party: Keypair, deadline: anchor.BN) {
+      return program.methods
+        .acceptDeadlineExtension(deadline)
+        .accounts({ job: jobPDA, party: party.publicKey })
+        .signers([party])
+        .rpc();
+    }
+
+    before(async () => {
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(1_000_000), "Extension test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      const job = await program.account.job.fetch(jobPDA);
+      newDeadline = job.deadline.add(new anchor.BN(3 * 86400));
+    });
+
+    it("rejects a proposal that does not move the deadline later", async () => {
+      const job = await program.account.job.fetch(jobPDA);
+      try {
+        await propose(agent, job.deadline);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidExtension");
+      }
+    });
+
+    it("SECURITY: only the job's parties can propose", async () => {
+      try {
+        await propose(unauthorizedUser, newDeadline);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("the proposer cannot accept their own extension", async () => {
+      await propose(agent, newDeadline);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.ok(job.extensionProposer.equals(agent.publicKey));
+      assert.ok(job.proposedDeadline.eq(newDeadline));
+
+      try {
+        await accept(agent, newDeadline);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("acceptance must match the proposed deadline", async () => {
+      try {
+        await accept(requester, newDeadline.add(new anchor.BN(1)));
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("NoExtensionProposed");
+      }
+    });
+
+    it("the other party accepts and the deadline moves", async () => {
+      await accept(requester, newDeadline);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.ok(job.deadline.eq(newDeadline));
+      assert.equal(job.extensions, 1);
+      assert.ok(job.extensionProposer.equals(PublicKey.default));
+    });
+  });
+
+  // ====================================================================
+  // 27. JOB ABANDONMENT TESTS
+  // ====================================================================
+
+  describe("Job Abandonment", () => {
+    const jobId = "abandon-job";
+    const AMOUNT = 1_000_000;
+    const BOND = 100_000;
+    let jobPDA: PublicKey;
+
+    function acceptWithBond() {
+      return program.methods
+        .acceptJob()
+        .accounts({
+          job: jobPDA,
+          agent: agent.publicKey,
+          bondToken: findBondTokenPDA(jobId)[0],
+          agentToken: agentToken,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([agent])
+        .rpc();
+    }
+
+    function abandon(signer: Keypair, profileOwner = signer.publicKey) {
+      return program.methods
+        .abandonJob()
+        .accounts({
+          job: jobPDA,
+          artifacts: findArtifactsPDA(jobPDA)[0],
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          agent: signer.publicKey,
+          agentProfile: findAgentProfilePDA(profileOwner)[0],
+          bondToken: findBondTokenPDA(jobId)[0],
+          requesterToken: requesterToken,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      await mintTo(provider.connection, admin, mint, agentToken, admin, 2 * BOND);
+
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(
+          jobId,
+          new anchor.BN(AMOUNT),
+          "Abandon test",
+          7,
+          terms({ bondAmount: new anchor.BN(BOND), maxRevisions: 1 })
+        )
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          bondToken: findBondTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await acceptWithBond();
+    });
+
+    it("SECURITY: only the assigned agent can abandon", async () => {
+      try {
+        await abandon(unauthorizedUser, agent.publicKey);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("reopens the job and forfeits the bond to the requester", async () => {
+      const profileBefore = await program.account.agentProfile.fetch(
+        findAgentProfilePDA(agent.publicKey)[0]
+      );
+      const requesterBefore = Number((await getAccount(provider.connection, requesterToken)).amount);
+
+      // Use up the revision allowance before walking away
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await program.methods.requestRevision(0, "Needs changes")
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+
+      await abandon(agent);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { open: {} });
+      assert.ok(job.agent.equals(PublicKey.default));
+      assert.equal(job.bondBalance.toNumber(), 0);
+      assert.equal(job.revisions, 0);
+      assert.equal(job.amount.toNumber(), AMOUNT);
+
+      const requesterAfter = Number((await getAccount(provider.connection, requesterToken)).amount);
+      assert.equal(requesterAfter - requesterBefore, BOND);
+
+      const profileAfter = await program.account.agentProfile.fetch(
+        findAgentProfilePDA(agent.publicKey)[0]
+      );
+      assert.equal(profileAfter.jobsAbandoned, profileBefore.jobsAbandoned + 1);
+    });
+
+    it("the reopened job can be accepted again with a fresh bond", async () => {
+      await acceptWithBond();
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { inProgress: {} });
+      assert.equal(job.bondBalance.toNumber(), BOND);
+    });
+  });
+
+  // ====================================================================
+  // 28. MUTUAL SETTLEMENT TESTS
+  // ====================================================================
+
+  describe("Mutual Settlement", () => {
+    const jobId = "settlement-job";
+    const AMOUNT = 1_000_000;
+    let jobPDA: PublicKey;
+
+    function propose(party: Keypair, agentPercentage: number) {
+      return program.methods
+        .proposeSettlement(agentPercentage, 0)
+        .accounts({ job: jobPDA, party: party.publicKey })
+        .signers([party])
+        .rpc();
+    }
+
+    function accept(party: Keypair, agentPercentage: number) {
+      return program.methods
+        .acceptSettlement(agentPercentage, 0)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken: findEscrowTokenPDA(jobId)[0],
+            agentToken: agentToken,
+            requesterToken: requesterToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            requesterEvidence: findEvidencePDA(jobPDA, requester.publicKey)[0],
+            agentEvidence: findEvidencePDA(jobPDA, agent.publicKey)[0],
+          },
+          party: party.publicKey,
+          disputeVote: findDisputeVotePDA(jobPDA)[0],
+        })
+        .signers([party])
+        .rpc();
+    }
+
+    before(async () => {
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(AMOUNT), "Settlement test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+    });
+
+    it("rejects a percentage above 100", async () => {
+      try {
+        await propose(requester, 101);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("InvalidPercentage");
+      }
+    });
+
+    it("the proposer cannot accept their own settlement", async () => {
+      await propose(requester, 40);
+      try {
+        await accept(requester, 40);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("acceptance must match the proposed split", async () => {
+      try {
+        await accept(agent, 60);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("NoSettlementProposed");
+      }
+    });
+
+    it("a pending settlement is dropped when the job changes status", async () => {
+      await propose(agent, 70);
+      await program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.ok(job.settlementProposer.equals(PublicKey.default));
+      try {
+        await accept(requester, 70);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("NoSettlementProposed");
+      }
+
+      // Proposed again against the job under review
+      await propose(requester, 40);
+    });
+
+    it("splits the escrow once the other party accepts", async () => {
+      const agentBefore = Number((await getAccount(provider.connection, agentToken)).amount);
+      const requesterBefore = Number((await getAccount(provider.connection, requesterToken)).amount);
+
+      await accept(agent, 40);
+
+      const agentAfter = Number((await getAccount(provider.connection, agentToken)).amount);
+      const requesterAfter = Number((await getAccount(provider.connection, requesterToken)).amount);
+      assert.equal(agentAfter - agentBefore, 400_000);
+      assert.equal(requesterAfter - requesterBefore, 600_000);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { resolved: {} });
+    });
+  });
+
+  // ====================================================================
+  // 29. ESCROW TOP-UP TESTS
+  // ====================================================================
+
+  describe("Escrow Top-Up", () => {
+    const jobId = "top-up-job";
+    const AMOUNT = 1_000_000;
+    let jobPDA: PublicKey;
+
+    function increase(signer: Keypair, signerToken: PublicKey, amount: number) {
+      return program.methods
+        .increaseAmount(new anchor.BN(amount))
+        .accounts({
+          job: jobPDA,
+          requester: signer.publicKey,
+          systemProgram: SystemProgram.programId,
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requesterToken: signerToken,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(AMOUNT), "Top-up test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+    });
+
+    it("adds funds to an in-progress job without losing its agent", async () => {
+      await increase(requester, requesterToken, 250_000);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.equal(job.amount.toNumber(), AMOUNT + 250_000);
+      assert.equal(job.milestones[job.milestones.length - 1].amount.toNumber(), AMOUNT + 250_000);
+      assert.ok(job.agent.equals(agent.publicKey));
+
+      const escrow = await getAccount(provider.connection, findEscrowTokenPDA(jobId)[0]);
+      assert.equal(Number(escrow.amount), AMOUNT + 250_000);
+    });
+
+    it("rejects a zero top-up", async () => {
+      try {
+        await increase(requester, requesterToken, 0);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("ZeroAmount");
+      }
+    });
+
+    it("SECURITY: only the requester can top up", async () => {
+      try {
+        await increase(agent, agentToken, 1_000);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("a top-up drops a pending settlement", async () => {
+      await program.methods
+        .proposeSettlement(50, 0)
+        .accounts({ job: jobPDA, party: agent.publicKey })
+        .signers([agent])
+        .rpc();
+
+      await increase(requester, requesterToken, 1_000);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.ok(job.settlementProposer.equals(PublicKey.default));
+    });
+  });
+
+  // ====================================================================
+  // 30. REVISION REQUEST TESTS
+  // ====================================================================
+
+  describe("Revision Requests", () => {
+    const jobId = "revision-job";
+    let jobPDA: PublicKey;
+
+    function submit() {
+      return program.methods.submitDeliverable(0, deliverable("https://x.com"), [])
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+    }
+
+    function requestRevision(feedback: string) {
+      return program.methods
+        .requestRevision(0, feedback)
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester])
+        .rpc();
+    }
+
+    before(async () => {
+      [jobPDA] = findJobPDA(jobId);
+      await program.methods
+        .createJob(jobId, new anchor.BN(1_000_000), "Revision test", 7, terms({ maxRevisions: 1 }))
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+      await submit();
+    });
+
+    it("rejection needs force_dispute while revisions remain", async () => {
+      try {
+        await program.methods.rejectWork(0, "Not yet", false)
+          .accounts({ job: jobPDA, requester: requester.publicKey })
+          .signers([requester]).rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("RevisionsRemaining");
+      }
+    });
+
+    it("sends the work back to the agent with feedback", async () => {
+      await requestRevision("Please add tests");
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { inProgress: {} });
+      assert.equal(job.revisions, 1);
+    });
+
+    it("stops at max_revisions, after which rejection opens a dispute", async () => {
+      await submit();
+      try {
+        await requestRevision("More changes");
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("RevisionLimitReached");
+      }
+
+      await program.methods.rejectWork(0, "Still incomplete", false)
+        .accounts({ job: jobPDA, requester: requester.publicKey })
+        .signers([requester]).rpc();
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { disputed: {} });
+    });
+  });
+
+  // ====================================================================
+  // 31. CONTENT-HASHED DELIVERABLE TESTS
+  // ====================================================================
+
+  describe("Content-Hashed Deliverables", () => {
+    const jobId = "artifacts-job";
+    let jobPDA: PublicKey;
+    let artifactsPDA: PublicKey;
+
+    function submit(main: any, artifacts: any[]) {
+      return program.methods.submitDeliverable(0, main, artifacts)
+        .accounts({ job: jobPDA, artifacts: artifactsPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+    }
+
+    before(async () => {
+      [jobPDA] = findJobPDA(jobId);
+      [artifactsPDA] = findArtifactsPDA(jobPDA);
+      await program.methods
+        .createJob(jobId, new anchor.BN(1_000_000), "Artifacts test", 7, terms())
+        .accounts({
+          job: jobPDA,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          escrowToken: findEscrowTokenPDA(jobId)[0],
+          requester: requester.publicKey,
+          requesterToken: requesterToken,
+          mint: mint,
+          config: findConfigPDA()[0],
+          treasuryToken: treasuryToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+
+      await program.methods.acceptJob()
+        .accounts({ job: jobPDA, agent: agent.publicKey })
+        .signers([agent]).rpc();
+    });
+
+    it("rejects a deliverable without a content hash", async () => {
+      try {
+        await submit(deliverable("https://x.com", { contentHash: Array(32).fill(0) }), []);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("MissingContentHash");
+      }
+    });
+
+    it("rejects more than 8 extra artifacts", async () => {
+      const artifacts = Array.from({ length: 9 }, (_, i) => deliverable(`ipfs://a${i}`));
+      try {
+        await submit(deliverable("https://x.com"), artifacts);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("TooManyArtifacts");
+      }
+    });
+
+    it("records the main deliverable and its extra artifacts", async () => {
+      await submit(deliverable("ipfs://report", { contentType: "application/pdf" }), [
+        deliverable("ipfs://source", { contentHash: Array(32).fill(8), contentType: "application/zip" }),
+        deliverable("ipfs://demo", { contentHash: Array(32).fill(9), contentType: "video/mp4" }),
+      ]);
+
+      const job = await program.account.job.fetch(jobPDA);
+      assert.deepEqual(job.status, { underReview: {} });
+      assert.equal(job.deliverable.uri, "ipfs://report");
+      assert.equal(job.deliverable.contentType, "application/pdf");
+      assert.equal(job.artifactCount, 2);
+
+      const record = await program.account.deliverableArtifacts.fetch(artifactsPDA);
+      assert.ok(record.job.equals(jobPDA));
+      assert.ok(record.agent.equals(agent.publicKey));
+      assert.equal(record.artifacts.length, 2);
+      assert.deepEqual(record.artifacts[1].contentHash, Array(32).fill(9));
+      assert.equal(record.artifacts[1].contentType, "video/mp4");
+    });
+
+    it("returns the artifacts rent to the agent when the job is closed", async () => {
+      const [escrowAuthority] = findEscrowAuthorityPDA(jobId);
+      const [escrowToken] = findEscrowTokenPDA(jobId);
+
+      await program.methods
+        .approveAndPay(0, 5)
+        .accounts({
+          payout: {
+            job: jobPDA,
+            escrowAuthority,
+            agentProfile: findAgentProfilePDA(agent.publicKey)[0],
+            escrowToken,
+            agentToken: agentToken,
+            treasuryToken: treasuryToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          requester: requester.publicKey,
+        })
+        .signers([requester])
+        .rpc();
+
+      await rateSettledJob(jobPDA);
+
+      const close = (agentWallet: PublicKey | null) =>
+        program.methods
+          .closeJob()
+          .accounts({
+            job: jobPDA,
+            escrowAuthority,
+            requester: requester.publicKey,
+            archive: null,
+            escrowToken,
+            requesterToken: requesterToken,
+            mint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            artifacts: artifactsPDA,
+            agent: agentWallet,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([requester])
+          .rpc();
+
+      // The record cannot be stranded by leaving out the agent's wallet
+      try {
+        await close(null);
+        assert.fail("Should have failed");
+      } catch (err) {
+        expect(err.toString()).to.contain("MissingAccount");
+      }
+
+      const rent = await provider.connection.getBalance(artifactsPDA);
+      const before = await provider.connection.getBalance(agent.publicKey);
+
+      await close(agent.publicKey);
+
+      assert.isNull(await provider.connection.getAccountInfo(artifactsPDA));
+      assert.equal(await provider.connection.getBalance(agent.publicKey), before + rent);
+    });
+  });
 });
@@ -0,0 +1,516 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { BankrunProvider } from "anchor-bankrun";
+import { Clock, ProgramTestContext, startAnchor } from "solana-bankrun";
+import { assert, expect } from "chai";
+import * as pda from "../app/pda";
+import IDL from "../target/idl/basilisk_escrow.json";
+
+// Paths that only open once a window has elapsed (review timeout, dispute
+// expiry, deadlines) run against an in-process bank whose clock can be
+// moved forward, which the local validator used by basilisk-escrow.ts
+// cannot do. Jobs here are native SOL so no token accounts are needed.
+
+describe("basilisk-escrow (clock warp)", () => {
+  const DAY = 86400;
+  const FEE_BPS = 250; // 2.5%
+  const AMOUNT = LAMPORTS_PER_SOL / 2;
+  const BOND = AMOUNT / 10;
+
+  // ── Test accounts ─────────────────────────────────────────────────────
+  const admin = Keypair.generate();
+  const arbitrator = Keypair.generate();
+  const arbitrator2 = Keypair.generate();
+  const requester = Keypair.generate();
+  const agent = Keypair.generate();
+
+  let context: ProgramTestContext;
+  let program: Program<any>;
+  let events: anchor.EventParser;
+
+  // ── PDA derivation helpers ────────────────────────────────────────────
+  function findConfigPDA(): [PublicKey, number] {
+    return pda.findConfigPDA(program.programId);
+  }
+
+  function findJobPDA(jobId: string): [PublicKey, number] {
+    return pda.findJobPDA(program.programId, requester.publicKey, jobId);
+  }
+
+  function findEscrowAuthorityPDA(jobId: string): [PublicKey, number] {
+    return pda.findEscrowAuthorityPDA(program.programId, requester.publicKey, jobId);
+  }
+
+  function findSolVaultPDA(jobId: string): [PublicKey, number] {
+    return pda.findSolVaultPDA(program.programId, requester.publicKey, jobId);
+  }
+
+  function findBondVaultPDA(jobId: string): [PublicKey, number] {
+    return pda.findBondVaultPDA(program.programId, requester.publicKey, jobId);
+  }
+
+  function findAgentProfilePDA(): [PublicKey, number] {
+    return pda.findAgentProfilePDA(program.programId, agent.publicKey);
+  }
+
+  function findSolTreasuryPDA(): [PublicKey, number] {
+    return pda.findSolTreasuryPDA(program.programId);
+  }
+
+  function findDisputeVotePDA(job: PublicKey): [PublicKey, number] {
+    return pda.findDisputeVotePDA(program.programId, job);
+  }
+
+  function findEvidencePDA(job: PublicKey, party: PublicKey): [PublicKey, number] {
+    return pda.findEvidencePDA(program.programId, job, party);
+  }
+
+  // ── Bank helpers ──────────────────────────────────────────────────────
+
+  // Move the bank's clock forward by `seconds`.
+  async function warp(seconds: number) {
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  }
+
+  async function now(): Promise<number> {
+    return Number((await context.banksClient.getClock()).unixTimestamp);
+  }
+
+  async function balance(account: PublicKey): Promise<number> {
+    return Number(await context.banksClient.getBalance(account));
+  }
+
+  async function exists(account: PublicKey): Promise<boolean> {
+    return (await context.banksClient.getAccount(account)) !== null;
+  }
+
+  // Process a method call directly, so its logs are available whether it
+  // succeeds or fails.
+  async function processCall(builder: any, signers: Keypair[]) {
+    const tx = await builder.transaction();
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = context.payer.publicKey;
+    tx.sign(context.payer, ...signers);
+    const { result, meta } = await context.banksClient.tryProcessTransaction(tx);
+    return { result, logs: meta?.logMessages ?? [] };
+  }
+
+  // Send a method call that must succeed; returns the events it emitted.
+  async function send(builder: any, signers: Keypair[] = []) {
+    const { result, logs } = await processCall(builder, signers);
+    assert.isNull(result, logs.join("\n"));
+    return [...events.parseLogs(logs)];
+  }
+
+  // Send a method call that must fail with `error`.
+  async function expectError(builder: any, signers: Keypair[], error: string) {
+    const { result, logs } = await processCall(builder, signers);
+    assert.isNotNull(result, "Should have failed");
+    expect(logs.join("\n")).to.contain(error);
+  }
+
+  // Optional job terms; defaults to a plain public, unbonded job.
+  function terms(overrides: Record<string, any> = {}) {
+    return {
+      milestones: [],
+      allowedAgents: [],
+      bondAmount: new anchor.BN(0),
+      bondBps: 0,
+      arbitrator: null,
+      arbitrationFee: null,
+      reviewDays: null,
+      maxRevisions: 0,
+      ...overrides,
+    };
+  }
+
+  // Content-hashed deliverable; the hash is arbitrary but non-zero.
+  function deliverable(uri: string) {
+    return { contentHash: Array(32).fill(7), uri, contentType: "text/html" };
+  }
+
+  // Create a bonded SOL job and accept it.
+  async function acceptedJob(jobId: string, jobTerms: Record<string, any> = {}) {
+    const [job] = findJobPDA(jobId);
+    await send(
+      program.methods
+        .createSolJob(
+          jobId,
+          new anchor.BN(AMOUNT),
+          "Clock warp test",
+          7,
+          terms({ bondAmount: new anchor.BN(BOND), ...jobTerms })
+        )
+        .accounts({
+          job,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          solVault: findSolVaultPDA(jobId)[0],
+          bondVault: findBondVaultPDA(jobId)[0],
+          config: findConfigPDA()[0],
+          solTreasury: findSolTreasuryPDA()[0],
+          requester: requester.publicKey,
+          systemProgram: SystemProgram.programId,
+        }),
+      [requester]
+    );
+    await send(
+      program.methods.acceptJob().accounts({
+        job,
+        agent: agent.publicKey,
+        agentProfile: findAgentProfilePDA()[0],
+        bondVault: findBondVaultPDA(jobId)[0],
+        systemProgram: SystemProgram.programId,
+      }),
+      [agent]
+    );
+    return job;
+  }
+
+  function submit(job: PublicKey, uri = "https://x.com") {
+    return send(
+      program.methods
+        .submitDeliverable(0, deliverable(uri), [])
+        .accounts({ job, agent: agent.publicKey }),
+      [agent]
+    );
+  }
+
+  // Create a bonded SOL job, accept it and submit the first deliverable.
+  async function submittedJob(jobId: string, jobTerms: Record<string, any> = {}) {
+    const job = await acceptedJob(jobId, jobTerms);
+    await submit(job);
+    return job;
+  }
+
+  // ── Setup ─────────────────────────────────────────────────────────────
+  before(async () => {
+    context = await startAnchor(
+      ".",
+      [],
+      [admin, arbitrator, arbitrator2, requester, agent].map((account) => ({
+        address: account.publicKey,
+        info: {
+          lamports: 10 * LAMPORTS_PER_SOL,
+          data: Buffer.alloc(0),
+          owner: SystemProgram.programId,
+          executable: false,
+        },
+      }))
+    );
+    program = new Program(IDL as any, new BankrunProvider(context));
+    events = new anchor.EventParser(program.programId, program.coder);
+
+    await send(
+      program.methods.initialize([arbitrator.publicKey], 1).accounts({
+        config: findConfigPDA()[0],
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      }),
+      [admin]
+    );
+    await send(
+      program.methods
+        .updateConfig(null, null, FEE_BPS, null, null, null, null)
+        .accounts({ config: findConfigPDA()[0], admin: admin.publicKey }),
+      [admin]
+    );
+  });
+
+  // ====================================================================
+  // 1. REVIEW TIMEOUT
+  // ====================================================================
+
+  describe("Review Timeout", () => {
+    it("pays the agent, fee and bond once the review window has elapsed", async () => {
+      const jobId = "warp-review-claim";
+      const job = await submittedJob(jobId, { reviewDays: 1 });
+      const [solTreasury] = findSolTreasuryPDA();
+      const fee = Math.floor((AMOUNT * FEE_BPS) / 10_000);
+
+      await warp(DAY + 1);
+
+      const agentBefore = await balance(agent.publicKey);
+      const treasuryBefore = await balance(solTreasury);
+      const vaultBefore = await balance(findSolVaultPDA(jobId)[0]);
+
+      // Permissionless: no signer beyond the fee payer
+      const emitted = await send(
+        program.methods.claimAfterReviewTimeout(0).accounts({
+          payout: {
+            job,
+            escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+            agentProfile: findAgentProfilePDA()[0],
+            solVault: findSolVaultPDA(jobId)[0],
+            agent: agent.publicKey,
+            solTreasury,
+            bondVault: findBondVaultPDA(jobId)[0],
+          },
+        })
+      );
+
+      assert.equal(await balance(agent.publicKey) - agentBefore, AMOUNT - fee + BOND);
+      assert.equal(await balance(solTreasury) - treasuryBefore, fee);
+      assert.equal(vaultBefore - await balance(findSolVaultPDA(jobId)[0]), AMOUNT);
+
+      const approved = emitted.find((event) => event.name === "jobApproved");
+      assert.ok(approved, "JobApproved not emitted");
+      assert.isTrue(approved.data.autoApproved);
+      assert.isTrue(approved.data.completed);
+      assert.equal(approved.data.rating, 0);
+      assert.equal(approved.data.amount.toNumber(), AMOUNT - fee);
+      assert.equal(approved.data.fee.toNumber(), fee);
+      assert.equal(approved.data.bondReturned.toNumber(), BOND);
+
+      const record = await program.account.job.fetch(job);
+      assert.deepEqual(record.status, { completed: {} });
+      assert.equal(record.bondBalance.toNumber(), 0);
+
+      // Completed without a rating
+      const profile = await program.account.agentProfile.fetch(findAgentProfilePDA()[0]);
+      assert.equal(profile.jobsCompleted, 1);
+      assert.equal(profile.ratingsCount, 0);
+    });
+  });
+
+  // ====================================================================
+  // 2. DISPUTE TIMEOUT
+  // ====================================================================
+
+  describe("Dispute Timeout", () => {
+    function updateConfig(
+      arbitrators: PublicKey[] | null,
+      threshold: number | null,
+      fallback: Record<string, number> | null
+    ) {
+      return send(
+        program.methods
+          .updateConfig(arbitrators, null, null, threshold, null, null, fallback)
+          .accounts({ config: findConfigPDA()[0], admin: admin.publicKey }),
+        [admin]
+      );
+    }
+
+    async function disputedJob(jobId: string) {
+      const job = await submittedJob(jobId);
+      await send(
+        program.methods.rejectWork(0, "Disputed", false).accounts({
+          job,
+          config: findConfigPDA()[0],
+          requester: requester.publicKey,
+        }),
+        [requester]
+      );
+      return job;
+    }
+
+    // Split payee accounts shared by resolve_dispute and expire_dispute
+    function payout(jobId: string) {
+      const [job] = findJobPDA(jobId);
+      return {
+        job,
+        escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+        agentProfile: findAgentProfilePDA()[0],
+        solVault: findSolVaultPDA(jobId)[0],
+        agent: agent.publicKey,
+        requester: requester.publicKey,
+        solTreasury: findSolTreasuryPDA()[0],
+        bondVault: findBondVaultPDA(jobId)[0],
+        requesterEvidence: findEvidencePDA(job, requester.publicKey)[0],
+        agentEvidence: findEvidencePDA(job, agent.publicKey)[0],
+      };
+    }
+
+    // Permissionless: the bank's fee payer expires the dispute
+    function expire(jobId: string, votePayer: PublicKey | null = null) {
+      const [job] = findJobPDA(jobId);
+      return send(
+        program.methods.expireDispute().accounts({
+          payout: payout(jobId),
+          config: findConfigPDA()[0],
+          caller: context.payer.publicKey,
+          disputeVote: findDisputeVotePDA(job)[0],
+          votePayer,
+        })
+      );
+    }
+
+    async function balances() {
+      return Promise.all(
+        [agent.publicKey, requester.publicKey, findSolTreasuryPDA()[0]].map(balance)
+      );
+    }
+
+    function fee(agentShare: number) {
+      return Math.floor((agentShare * FEE_BPS) / 10_000);
+    }
+
+    after(async () => {
+      await updateConfig([arbitrator.publicKey], 1, {
+        days: 14,
+        agentPercentage: 50,
+        bondAgentPercentage: 50,
+      });
+    });
+
+    it("applies the default 50/50 split, closing the partial vote and evidence", async () => {
+      const jobId = "warp-expire-default";
+      await updateConfig([arbitrator.publicKey, arbitrator2.publicKey], 2, null);
+      const job = await disputedJob(jobId);
+
+      for (const [party, n] of [[requester, 1], [agent, 2]] as [Keypair, number][]) {
+        await send(
+          program.methods.submitEvidence(Array(32).fill(n), `ipfs://evidence-${n}`).accounts({
+            job,
+            evidence: findEvidencePDA(job, party.publicKey)[0],
+            party: party.publicKey,
+            systemProgram: SystemProgram.programId,
+          }),
+          [party]
+        );
+      }
+
+      // One of two required votes: recorded, not executed
+      await send(
+        program.methods.resolveDispute(90, 100).accounts({
+          payout: payout(jobId),
+          config: findConfigPDA()[0],
+          arbitrator: arbitrator.publicKey,
+          disputeVote: findDisputeVotePDA(job)[0],
+          systemProgram: SystemProgram.programId,
+        }),
+        [arbitrator]
+      );
+
+      await warp(14 * DAY + 1);
+
+      const [disputeVote] = findDisputeVotePDA(job);
+      const [requesterEvidence] = findEvidencePDA(job, requester.publicKey);
+      const [agentEvidence] = findEvidencePDA(job, agent.publicKey);
+      const voteRent = await balance(disputeVote);
+      const requesterEvidenceRent = await balance(requesterEvidence);
+      const agentEvidenceRent = await balance(agentEvidence);
+      const [agentBefore, requesterBefore, treasuryBefore] = await balances();
+      const arbitratorBefore = await balance(arbitrator.publicKey);
+
+      const emitted = await expire(jobId, arbitrator.publicKey);
+
+      const half = AMOUNT / 2;
+      const [agentAfter, requesterAfter, treasuryAfter] = await balances();
+      assert.equal(agentAfter - agentBefore, half - fee(half) + BOND / 2 + agentEvidenceRent);
+      assert.equal(requesterAfter - requesterBefore, half + BOND / 2 + requesterEvidenceRent);
+      assert.equal(treasuryAfter - treasuryBefore, fee(half));
+
+      // The vote record's rent goes back to the arbitrator who paid it
+      assert.equal(await balance(arbitrator.publicKey) - arbitratorBefore, voteRent);
+      assert.isFalse(await exists(disputeVote));
+      assert.isFalse(await exists(requesterEvidence));
+      assert.isFalse(await exists(agentEvidence));
+
+      const expired = emitted.find((event) => event.name === "disputeExpired");
+      assert.ok(expired, "DisputeExpired not emitted");
+      assert.equal(expired.data.agentPercentage, 50);
+      assert.equal(expired.data.bondAgentAmount.toNumber(), BOND / 2);
+
+      const record = await program.account.job.fetch(job);
+      assert.deepEqual(record.status, { resolved: {} });
+      assert.equal(record.bondBalance.toNumber(), 0);
+
+      await updateConfig([arbitrator.publicKey], 1, null);
+    });
+
+    it("applies a configured fallback that awards the agent the escrow", async () => {
+      const jobId = "warp-expire-agent";
+      await updateConfig(null, null, { days: 3, agentPercentage: 100, bondAgentPercentage: 0 });
+      await disputedJob(jobId);
+
+      await warp(3 * DAY + 1);
+      const [agentBefore, requesterBefore, treasuryBefore] = await balances();
+
+      await expire(jobId);
+
+      const [agentAfter, requesterAfter, treasuryAfter] = await balances();
+      assert.equal(agentAfter - agentBefore, AMOUNT - fee(AMOUNT));
+      assert.equal(requesterAfter - requesterBefore, BOND);
+      assert.equal(treasuryAfter - treasuryBefore, fee(AMOUNT));
+    });
+
+    it("applies a configured fallback that refunds the requester", async () => {
+      const jobId = "warp-expire-requester";
+      await updateConfig(null, null, { days: 3, agentPercentage: 0, bondAgentPercentage: 100 });
+      await disputedJob(jobId);
+
+      await warp(3 * DAY + 1);
+      const [agentBefore, requesterBefore, treasuryBefore] = await balances();
+
+      await expire(jobId);
+
+      const [agentAfter, requesterAfter, treasuryAfter] = await balances();
+      assert.equal(agentAfter - agentBefore, BOND);
+      assert.equal(requesterAfter - requesterBefore, AMOUNT);
+      assert.equal(treasuryAfter - treasuryBefore, 0);
+    });
+  });
+
+  // ====================================================================
+  // 3. REVISION REQUESTS
+  // ====================================================================
+
+  describe("Revision Requests", () => {
+    it("SECURITY: a revision just before the deadline leaves time to resubmit", async () => {
+      const jobId = "warp-revision";
+      const job = await acceptedJob(jobId, { maxRevisions: 1, reviewDays: 1 });
+      const { deadline } = await program.account.job.fetch(job);
+
+      // Work lands two hours before the deadline and is sent back at once
+      await warp(deadline.toNumber() - (await now()) - 2 * 3600);
+      await submit(job);
+      const emitted = await send(
+        program.methods
+          .requestRevision(0, "Please add tests")
+          .accounts({ job, requester: requester.publicKey }),
+        [requester]
+      );
+
+      const revised = await program.account.job.fetch(job);
+      const resubmitBy = (await now()) + DAY;
+      assert.deepEqual(revised.status, { inProgress: {} });
+      assert.equal(revised.deadline.toNumber(), resubmitBy);
+      const requested = emitted.find((event) => event.name === "revisionRequested");
+      assert.ok(requested, "RevisionRequested not emitted");
+      assert.equal(requested.data.resubmitBy.toNumber(), resubmitBy);
+
+      // Past the original deadline the job is still live
+      await warp(2 * 3600 + 1);
+      await expectError(
+        program.methods.cancelJob().accounts({
+          job,
+          escrowAuthority: findEscrowAuthorityPDA(jobId)[0],
+          requester: requester.publicKey,
+          solVault: findSolVaultPDA(jobId)[0],
+          bondVault: findBondVaultPDA(jobId)[0],
+        }),
+        [requester],
+        "CannotCancel"
+      );
+
+      await submit(job, "https://x.com/v2");
+      const resubmitted = await program.account.job.fetch(job);
+      assert.deepEqual(resubmitted.status, { underReview: {} });
+    });
+  });
+});